@@ -0,0 +1,135 @@
+# Deutscher Katalog, Platzhalter werden mit { $name } eingefügt
+
+default-title = Willkommen im Chatraum!
+default-message = -> Schreibe '/register {"{"}name{"}"}' um dich zu registrieren
+register-output = -> Du kannst nun Nachrichten schreiben oder Commands ausführen
+register-help-output = [ '/help' → Befehle anzeigen ]
+register-quit-output = [ '/quit' → Chat verlassen ]
+unregistered = - Du bist nun vom Server getrennt -
+register-first = Bitte registriere dich, um die Clients zu sehen.
+no-group = keine Gruppe
+
+title-registered = Du bist registriert { $name }!
+title-group = { $name }, du bist in der Gruppe { $group }!
+
+user-left = { $name } hat den Chat verlassen
+user-joined = { $name } ist dem Chat beigetreten
+group-joined = -> Du bist nun Teil der Gruppe { $group }
+group-private-hint = {" "}[ Private Nachrichten kannst du weiterhin außerhalb verschicken ]
+
+new-messages-below = { $count ->
+    [one] ↓ eine neue Nachricht
+   *[other] ↓ { $count } neue Nachrichten
+}
+
+theme-list = -> Verfügbare Themes: { $themes }
+theme-switched = -> Theme gewechselt zu { $theme }
+
+tab-chat = Chat
+tab-mentions = Erwähnungen
+tab-users = Nutzer
+tab-help = Hilfe
+
+header-name = Name
+header-call = Anruf
+header-group = Gruppe
+header-place = Ort
+header-message = Nachricht
+header-command = Befehl
+header-description = Beschreibung
+
+help-info = /help, /users und /group users findest du in den Tabs
+help-register = registriert einen Client
+help-quit = meldet ab und beendet den Chat
+help-private = sendet eine private Nachricht
+help-theme = wechselt das Theme, ohne Namen werden die Themes aufgelistet
+help-plugins = listet die Befehle der Skripte auf oder lädt sie neu
+help-alias = legt ein Alias an, z.B. /alias hi = /group join $1; hallo $*
+help-middleware = listet die Middleware auf oder schaltet eine an oder aus
+help-outbox = listet die ungesendeten Nachrichten auf, bricht eine ab oder sendet sie erneut
+help-edit = ändert den Text deiner letzten Nachricht
+help-delete = löscht deine letzte Nachricht
+help-react = fügt einer Nachricht eine Reaktion hinzu oder nimmt sie zurück, private Nachrichten brauchen die Client-ID des anderen Clients
+help-aliases = auch { $aliases }
+
+action-quit = beenden
+action-next-tab = nächster Tab
+action-previous-tab = vorheriger Tab
+action-send = senden
+action-newline = neue Zeile
+action-scroll-up = hoch scrollen
+action-scroll-down = runter scrollen
+action-page-up = Seite hoch
+action-page-down = Seite runter
+action-scroll-top = zum Anfang
+action-scroll-bottom = zum Ende
+action-history-previous = vorherige Eingabe
+action-history-next = letzte Eingabe
+action-edit-last = letzte Nachricht bearbeiten
+action-select-previous = vorherige Nachricht wählen
+action-select-next = nächste Nachricht wählen
+action-react = reagieren
+action-reply = antworten
+action-row-up = hoch
+action-row-down = runter
+action-open-mention = Nachricht zeigen
+action-refresh-users = Nutzer aktualisieren
+action-group-users = nur Gruppenmitglieder
+group-left = -> Du hast die Gruppe verlassen
+headless-unknown-user = Der Nutzer { $name } ist nicht online
+headless-timeout = Es kamen nur { $received } von { $expected } Antworten an
+bot-dice = { $name } würfelt { $dice }: { $rolls } (Summe { $total })
+bot-dice-usage = Benutzung: !dice [Anzahl]d[Seiten], höchstens { $max } Würfel
+bot-remind = Erinnerung: { $text }
+bot-remind-set = Ich erinnere dich in { $time }
+bot-remind-usage = Benutzung: !remind 10m Text (s, m oder h)
+scripts-loaded = { $count ->
+    [one] Ein Skript-Befehl geladen
+   *[other] { $count } Skript-Befehle geladen
+}
+scripts-none = Keine Skripte im plugins-Ordner gefunden
+scripts-usage = Benutzung: /plugins [list | reload]
+script-command-taken = Der Befehl { $command } aus { $file } ist schon vergeben
+plugin-usage = { $problem }. Benutzung: { $usage }
+arg-missing = { $arg } fehlt
+arg-number = { $arg } muss eine Zahl sein
+arg-choice = { $arg } muss eines von { $choices } sein
+arg-too-many = Zu viele Argumente
+aliases-none = Keine Aliase angelegt
+alias-usage = Benutzung: /alias {"{"}name{"}"} = {"{"}befehle{"}"}, ohne Befehle wird das Alias gelöscht
+alias-invalid = { $name } ist kein gültiger Alias-Name
+alias-taken = { $name } ist schon ein Befehl
+alias-removed = Das Alias { $name } wurde gelöscht
+alias-unknown = Es gibt kein Alias { $name }
+alias-set = Das Alias { $name } wurde gespeichert
+alias-help = führt aus: { $expansion }
+middleware-none = Keine Middleware registriert
+middleware-usage = Benutzung: /middleware on|off {"{"}name{"}"}
+middleware-unknown = Es gibt keine Middleware { $name }
+middleware-enabled = { $name } ist angeschaltet
+middleware-disabled = { $name } ist ausgeschaltet
+outbox-empty = Alle Nachrichten wurden gesendet
+outbox-pending = #{ $id } wartet: { $text }
+outbox-failed = #{ $id } fehlgeschlagen ({ $error }): { $text }
+outbox-usage = Benutzung: /outbox cancel|resend {"{"}nummer{"}"}
+outbox-unknown = Es gibt keine Nachricht #{ $id }, die geändert werden kann
+outbox-cancelled = Nachricht #{ $id } wurde abgebrochen
+outbox-resent = Nachricht #{ $id } wird erneut gesendet
+edit-nothing = Du hast keine Nachricht gesendet, die geändert werden kann
+message-edited = (bearbeitet)
+message-deleted = Nachricht gelöscht
+reaction-picker = Reagieren
+replying-to = Antwort an { $name }: { $excerpt } – [Esc] abbrechen
+mentions-empty = Dich hat noch niemand erwähnt
+mention-private = privat
+mention-everyone = alle
+mentions-unread = { $count ->
+    [one] @ eine Erwähnung
+   *[other] @ { $count } Erwähnungen
+}
+window-title = Chat
+window-title-unread = ({ $count }) Chat
+call-incoming = -> { $name } ruft dich an
+call-incoming-text = ruft dich an
+typing-one = { $name } schreibt…
+typing-many = { $names } schreiben…
@@ -0,0 +1,135 @@
+# English catalog, arguments are inserted with { $name }
+
+default-title = Welcome to the chatroom!
+default-message = -> Type '/register {"{"}name{"}"}' to register
+register-output = -> You can now write messages or execute commands
+register-help-output = [ '/help' → show commands ]
+register-quit-output = [ '/quit' → leave the chat ]
+unregistered = - You are now disconnected from the server -
+register-first = Please register to see the clients.
+no-group = no group
+
+title-registered = You are registered { $name }!
+title-group = { $name }, you are in the group { $group }!
+
+user-left = { $name } left the chat
+user-joined = { $name } joined the chat
+group-joined = -> You are now part of the group { $group }
+group-private-hint = {" "}[ You can still send private messages outside of the group ]
+
+new-messages-below = { $count ->
+    [one] ↓ one new message
+   *[other] ↓ { $count } new messages
+}
+
+theme-list = -> Available themes: { $themes }
+theme-switched = -> Switched theme to { $theme }
+
+tab-chat = Chat
+tab-mentions = Mentions
+tab-users = Users
+tab-help = Help
+
+header-name = Name
+header-call = Call
+header-group = Group Name
+header-place = Place
+header-message = Message
+header-command = Command
+header-description = Description
+
+help-info = /help & /users & /group users are moved to the tabs
+help-register = registers a client
+help-quit = unregisters and quits the chat
+help-private = sends a private message
+help-theme = switches the theme, without a name the themes are listed
+help-plugins = lists the commands of the scripts or reloads them
+help-alias = defines an alias, e.g. /alias hi = /group join $1; hello $*
+help-middleware = lists the middleware or switches one on or off
+help-outbox = lists the unsent messages, cancels or resends one
+help-edit = changes the text of your last message
+help-delete = deletes your last message
+help-react = adds a reaction to a message or takes it back, private messages need the client id of the other client
+help-aliases = also { $aliases }
+
+action-quit = quit
+action-next-tab = next tab
+action-previous-tab = previous tab
+action-send = send
+action-newline = new line
+action-scroll-up = scroll up
+action-scroll-down = scroll down
+action-page-up = page up
+action-page-down = page down
+action-scroll-top = scroll to top
+action-scroll-bottom = scroll to bottom
+action-history-previous = previous input
+action-history-next = last input
+action-edit-last = edit last message
+action-select-previous = select previous message
+action-select-next = select next message
+action-react = react
+action-reply = reply
+action-row-up = move up
+action-row-down = move down
+action-open-mention = open message
+action-refresh-users = refresh users
+action-group-users = only group users
+group-left = -> You left the group
+headless-unknown-user = The user { $name } is not online
+headless-timeout = Only { $received } of { $expected } replies arrived
+bot-dice = { $name } rolls { $dice }: { $rolls } (total { $total })
+bot-dice-usage = Usage: !dice [count]d[sides], at most { $max } dice
+bot-remind = Reminder: { $text }
+bot-remind-set = I will remind you in { $time }
+bot-remind-usage = Usage: !remind 10m text (s, m or h)
+scripts-loaded = { $count ->
+    [one] Loaded one script command
+   *[other] Loaded { $count } script commands
+}
+scripts-none = No scripts found in the plugins directory
+scripts-usage = Usage: /plugins [list | reload]
+script-command-taken = The command { $command } of { $file } is already taken
+plugin-usage = { $problem }. Usage: { $usage }
+arg-missing = { $arg } is missing
+arg-number = { $arg } has to be a number
+arg-choice = { $arg } has to be one of { $choices }
+arg-too-many = Too many arguments
+aliases-none = No aliases defined
+alias-usage = Usage: /alias {"{"}name{"}"} = {"{"}expansion{"}"}, an empty expansion removes the alias
+alias-invalid = { $name } is no valid alias name
+alias-taken = { $name } is already a command
+alias-removed = The alias { $name } has been removed
+alias-unknown = There is no alias { $name }
+alias-set = The alias { $name } has been saved
+alias-help = runs: { $expansion }
+middleware-none = No middleware registered
+middleware-usage = Usage: /middleware on|off {"{"}name{"}"}
+middleware-unknown = There is no middleware { $name }
+middleware-enabled = { $name } is switched on
+middleware-disabled = { $name } is switched off
+outbox-empty = All messages have been sent
+outbox-pending = #{ $id } waiting: { $text }
+outbox-failed = #{ $id } failed ({ $error }): { $text }
+outbox-usage = Usage: /outbox cancel|resend {"{"}number{"}"}
+outbox-unknown = There is no message #{ $id } which can be changed
+outbox-cancelled = Message #{ $id } has been cancelled
+outbox-resent = Message #{ $id } will be sent again
+edit-nothing = You have not sent a message which could be changed
+message-edited = (edited)
+message-deleted = message deleted
+reaction-picker = React
+replying-to = replying to { $name }: { $excerpt } – [Esc] cancel
+mentions-empty = Nobody has mentioned you yet
+mention-private = private
+mention-everyone = everyone
+mentions-unread = { $count ->
+    [one] @ one mention
+   *[other] @ { $count } mentions
+}
+window-title = Chat
+window-title-unread = ({ $count }) Chat
+call-incoming = -> { $name } is calling you
+call-incoming-text = is calling you
+typing-one = { $name } is typing…
+typing-many = { $names } are typing…
@@ -1,34 +1,99 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::UI::hyperlinks::{self, Hyperlink};
+use crate::UI::keymap::{Action, KeyScope, Keymap};
+use crate::UI::markdown;
+use crate::UI::message_buffer::{ChatText, DEFAULT_MAX_MESSAGES, MessageBuffer, Render};
+use crate::UI::notifier::Notifier;
+use crate::UI::reactions::{ReactionPicker, reaction_footer};
 use crate::UI::tabs::help::{HelpItem, HelpTable};
+use crate::UI::tabs::mentions::{MentionItem, MentionsTable};
 use crate::UI::tabs::tabs::SelectedTab;
 use crate::UI::tabs::users::UsersTable;
-use crate::service::user_service::UserService;
-use crate::types::*;
+use crate::UI::theme::{self, Theme};
+use crate::UI::typing::Typing;
+use crate::UI::{
+    input_history::InputHistory,
+    user_interface::{
+        accent_span, dim_span, error_span, info_span, name_span, sender_spans, success_span,
+        translated_line,
+    },
+};
 use crate::{
     UI::event::{AppEvent, Event, EventHandler},
     UI::user_interface,
-    types::Response,
-};
-use crate::{
-    UI::{
-        input_history::InputHistory,
-        user_interface::{blue_span, purple_span, red_span, turkis_span},
-    },
-    helper::lines_from_string,
 };
 use color_eyre::eyre::Ok;
+use ratatui::crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Position, Rect};
 use ratatui::style::{Color, Style};
+use ratatui::text::Text;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
-use ratatui::{
-    DefaultTerminal,
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
-};
-use ratatui::{layout::Size, text::Text};
+use ratatui::{DefaultTerminal, crossterm::event::KeyEvent};
+use rust_chat_client::chat::control;
+use rust_chat_client::chat::edits::{self, ChatEdit};
+use rust_chat_client::chat::mentions;
+use rust_chat_client::chat::notifications::{Notification, NotificationKind};
+use rust_chat_client::chat::outbox::{OutboxState, OutboxUpdate, QueuedMessage};
+use rust_chat_client::chat::reactions::{self, Reactions};
+use rust_chat_client::chat::receipts::{self, DeliveryStatus, DeliveryUpdate};
+use rust_chat_client::chat::replies::{self, ReplyTo};
+use rust_chat_client::config::settings::Settings;
+use rust_chat_client::i18n::catalog::{tr, tr_args};
+use rust_chat_client::service::user_service::UserService;
+use rust_chat_client::types::*;
+use rust_chat_client::{helper::lines_from_string, types::Response};
 use tokio::sync::mpsc::Receiver;
 use tui_textarea::{CursorMove, TextArea};
 
+// lines scrolled per mouse wheel step
+const MOUSE_SCROLL_LINES: isize = 3;
+// non-breaking spaces, the wrapping of the chat trims normal ones
+pub const INDENT: &str = "\u{a0}\u{a0}";
+
+// an own private message in the chat, its line shows the delivery status
+#[derive(Debug)]
+struct SentPrivate {
+    line_id: usize,
+    receiver: String,
+    receiver_id: String,
+    text: String,
+    status: DeliveryStatus,
+    edited: bool,
+}
+
+impl SentPrivate {
+    fn spans(&self, badge: bool) -> Vec<Span<'static>> {
+        sender_spans(self.receiver.clone(), &self.receiver_id, badge)
+    }
+
+    fn render(&self, badge: bool) -> Render {
+        let (receiver, receiver_id) = (self.receiver.clone(), self.receiver_id.clone());
+        let (text, status, edited) = (self.text.clone(), self.status, self.edited);
+        Render::new(move || {
+            private_line(
+                sender_spans(receiver.clone(), &receiver_id, badge),
+                &text,
+                status,
+                edited,
+            )
+        })
+    }
+}
+
+// a received message with an id, which its sender can edit or delete
+#[derive(Debug)]
+struct ChatLine {
+    line_id: usize,
+    client_id: String,
+    name: String,
+    text: String,
+    // reactions to private messages are only sent to their sender
+    private: bool,
+}
+
 /// Application.
 #[derive(Debug)]
 pub struct App<'a> {
@@ -36,43 +101,111 @@ pub struct App<'a> {
     pub running: bool,
     /// Event handler.
     pub events: EventHandler,
-    pub messages: Text<'a>,
+    pub messages: MessageBuffer,
     pub title: Line<'a>,
     pub user_service: Arc<UserService>,
     pub text_input: TextArea<'static>,
     pub vertical_scroll_state: ScrollbarState,
     pub vertical_scroll: usize,
-    pub chat_size: Size,
+    // sticks the chat to the newest message until the user scrolls up
+    pub follow_tail: bool,
+    // messages which arrived while the user was scrolled up
+    pub unseen_messages: usize,
+    pub chat_area: Rect,
+    pub tabs_area: Rect,
+    // names of the clients seen so far with their client ids, used to open private chats
+    pub known_clients: HashMap<String, String>,
     pub history: InputHistory,
     pub selected_tab: SelectedTab,
+    pub mentions_table: MentionsTable,
     pub users_table: UsersTable,
     pub help_table: HelpTable,
+    // users who are typing and the throttle of the own notifications
+    pub typing: Typing,
+    // own private messages which wait for receipts, by message id
+    sent_private: HashMap<String, SentPrivate>,
+    // received private messages (client id, message id) which have not been read yet
+    unread_private: Vec<(String, String)>,
+    // lines of queued group messages, by outbox id
+    queued_lines: HashMap<u64, usize>,
+    // received messages which can still be edited, by message id
+    chat_lines: HashMap<String, ChatLine>,
+    // reactions to the messages in the chat
+    reactions: Reactions,
+    // message id of the selected message
+    selected: Option<String>,
+    pub reaction_picker: Option<ReactionPicker>,
+    // the message which the next chat message answers
+    pub replying: Option<ReplyTo>,
+    // the typed name and the index of the shown match while @names are completed
+    completion: Option<(String, usize)>,
+    notifier: Notifier,
+    // links of the last frame, they are written after it has been drawn
+    pub hyperlinks: Vec<Hyperlink>,
+    pub write_hyperlinks: bool,
+    // initials in front of the names
+    pub name_badges: bool,
+    pub keymap: Keymap,
+    // the terminal is only drawn again if the state has changed
+    pub needs_redraw: bool,
 }
 
 // TODO webrtc?
 impl<'a> App<'a> {
     /// Constructs a new instance of [`App`].
-    pub fn new(user_service: Arc<UserService>, mut receiver: Receiver<Response>) -> Self {
+    pub fn new(
+        user_service: Arc<UserService>,
+        mut receiver: Receiver<Response>,
+        keymap: Keymap,
+        settings: &Settings,
+    ) -> Self {
+        let mut messages =
+            MessageBuffer::new(settings.max_messages.unwrap_or(DEFAULT_MAX_MESSAGES));
+        messages.push(Render::text(|| info_span(tr("default-message"))));
         let mut app = Self {
             running: true,
             events: EventHandler::new(),
-            messages: Text::from(vec![Line::from(blue_span(DEFAULT_MESSAGE.to_string()))]),
+            messages,
             user_service: user_service.clone(),
             text_input: TextArea::default(),
             vertical_scroll: 0,
             vertical_scroll_state: ScrollbarState::default(),
-            chat_size: Size::new(0, 0),
-            title: Line::raw(DEFAULT_TITLE),
+            follow_tail: true,
+            unseen_messages: 0,
+            chat_area: Rect::default(),
+            tabs_area: Rect::default(),
+            known_clients: HashMap::new(),
+            title: Line::raw(tr("default-title")),
             history: InputHistory {
                 current: -1,
                 first: false,
                 inputs: Vec::new(),
             },
             selected_tab: SelectedTab::Chat,
+            mentions_table: MentionsTable::new(),
             users_table: UsersTable::new(),
             help_table: HelpTable::new(),
+            typing: Typing::default(),
+            sent_private: HashMap::new(),
+            unread_private: Vec::new(),
+            queued_lines: HashMap::new(),
+            chat_lines: HashMap::new(),
+            reactions: Reactions::new(),
+            selected: None,
+            reaction_picker: None,
+            replying: None,
+            completion: None,
+            notifier: Notifier::new(settings.notifications.clone()),
+            hyperlinks: Vec::new(),
+            write_hyperlinks: settings.hyperlinks.unwrap_or(true),
+            name_badges: settings.name_badges.unwrap_or(false),
+            keymap,
+            needs_redraw: true,
         };
 
+        app.users_table.name_badges = app.name_badges;
+        app.help_table.set_local_items(local_help(&user_service));
+
         tokio::spawn(async move {
             user_service.chat_client.clone().response_poller().await;
         });
@@ -92,18 +225,34 @@ impl<'a> App<'a> {
     /// Run the application's main loop.
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
         while self.running {
-            terminal.draw(|frame| user_interface::render_ui(&mut self, frame))?;
+            if self.needs_redraw {
+                terminal.draw(|frame| user_interface::render_ui(&mut self, frame))?;
+                hyperlinks::write(terminal.backend_mut(), &self.hyperlinks)?;
+                self.needs_redraw = false;
+            }
 
             if self.text_input.is_empty() {
                 self.history.save_input(String::new());
             }
-            match self.events.next().await? {
-                Event::Tick => self.tick(),
-                Event::Crossterm(event) => {
-                    if let crossterm::event::Event::Key(key_event) = event {
-                        self.handle_key_events(key_event).await?;
+            let event = self.events.next().await?;
+            // every event except the tick changes the state
+            if !matches!(event, Event::Tick) {
+                self.needs_redraw = true;
+            }
+            match event {
+                Event::Tick => self.needs_redraw |= self.tick(),
+                Event::Crossterm(event) => match event {
+                    crossterm::event::Event::Key(key_event) => {
+                        self.handle_key_events(key_event).await?
                     }
-                }
+                    crossterm::event::Event::Mouse(mouse_event) => {
+                        self.handle_mouse_events(mouse_event).await
+                    }
+                    crossterm::event::Event::Paste(text) => self.paste(&text).await,
+                    crossterm::event::Event::FocusGained => self.notifier.focus_changed(true),
+                    crossterm::event::Event::FocusLost => self.notifier.focus_changed(false),
+                    _ => {}
+                },
                 Event::App(app_event) => match app_event {
                     AppEvent::Quit => self.quit().await,
                     AppEvent::Enter => self.handle_message().await,
@@ -117,95 +266,206 @@ impl<'a> App<'a> {
     // sendet AppEvents
     /// Handles the key events and updates the state of [`App`].
     pub async fn handle_key_events(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
-        match key_event.code {
-            KeyCode::Char('<') => {
-                self.next_tab();
-                self.update_users_tab().await;
-                self.update_help_tab().await;
+        // the reaction picker takes all keys while it is open
+        if let Some(picker) = &mut self.reaction_picker {
+            match key_event.code {
+                KeyCode::Left => picker.previous(),
+                KeyCode::Right => picker.next(),
+                KeyCode::Enter => self.react(),
+                KeyCode::Char(c) if picker.select_digit(c) => self.react(),
+                KeyCode::Esc => self.reaction_picker = None,
+                _ => {}
             }
-            KeyCode::Char('>') => {
-                self.previous_tab();
-                self.update_users_tab().await;
-                self.update_help_tab().await;
+            return Ok(());
+        }
+        if self.replying.is_some() && key_event.code == KeyCode::Esc {
+            self.replying = None;
+            return Ok(());
+        }
+        // tab completes an @name in front of the cursor instead of switching the tab
+        if key_event.code != KeyCode::Tab {
+            self.completion = None;
+        } else if matches!(self.selected_tab, SelectedTab::Chat) && self.complete_mention().await {
+            return Ok(());
+        }
+        let scope = KeyScope::from(self.selected_tab);
+        match self.keymap.action_for(scope, &key_event) {
+            Some(action) => self.handle_action(action).await,
+            None => match self.selected_tab {
+                SelectedTab::Chat | SelectedTab::Users => {
+                    if self.text_input.input(key_event) {
+                        self.notify_typing().await;
+                    }
+                }
+                SelectedTab::Mentions | SelectedTab::Help => {}
+            },
+        }
+        Ok(())
+    }
+
+    /// Executes an [`Action`] triggered by a key binding.
+    pub async fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.events.send(AppEvent::Quit),
+            Action::NextTab => self.select_tab(self.selected_tab.next()).await,
+            Action::PreviousTab => self.select_tab(self.selected_tab.previous()).await,
+            Action::Send => self.events.send(AppEvent::Enter),
+            Action::Newline => {
+                self.text_input.insert_newline();
             }
-            KeyCode::Esc => self.events.send(AppEvent::Quit),
-            KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
-                self.events.send(AppEvent::Quit)
+            Action::ScrollUp => self.scroll_by(-1),
+            Action::ScrollDown => self.scroll_by(1),
+            Action::PageUp => self.scroll_by(-self.page_height()),
+            Action::PageDown => self.scroll_by(self.page_height()),
+            Action::ScrollTop => self.scroll_by(isize::MIN),
+            Action::ScrollBottom => self.scroll_to_bottom(),
+            Action::HistoryPrevious | Action::HistoryNext => {
+                let str = self.search_input_history(action);
+                self.text_input = TextArea::default();
+                self.text_input.insert_str(str);
             }
-            _ => {
+            Action::EditLast => {
+                // the last own message is put into the input to be changed
+                if let Some(sent) = self.user_service.chat_client.sent_messages.last() {
+                    self.text_input = TextArea::default();
+                    self.text_input.insert_str(format!("/edit {}", sent.text));
+                }
+            }
+            Action::SelectPrevious => self.select_message(false),
+            Action::SelectNext => self.select_message(true),
+            Action::React => {
+                if let Some((id, receiver)) = self.selected_target() {
+                    self.reaction_picker = Some(ReactionPicker::new(id, receiver));
+                }
+            }
+            Action::Reply => self.reply_to_selected().await,
+            Action::RowUp => match self.selected_tab {
+                SelectedTab::Mentions => self.mentions_table.previous_row(),
+                SelectedTab::Users => self.users_table.previous_row(),
+                SelectedTab::Help => self.help_table.previous_row(),
+                SelectedTab::Chat => {}
+            },
+            Action::RowDown => match self.selected_tab {
+                SelectedTab::Mentions => self.mentions_table.next_row(),
+                SelectedTab::Users => self.users_table.next_row(),
+                SelectedTab::Help => self.help_table.next_row(),
+                SelectedTab::Chat => {}
+            },
+            Action::OpenMention => self.open_mention().await,
+            Action::RefreshUsers => {
+                if *self.user_service.chat_client.registered.lock().await {
+                    let user_service = self.user_service.clone();
+                    tokio::spawn(async move {
+                        user_service.executor("/users").await;
+                    });
+                }
+            }
+            Action::GroupUsers => {
+                if *self.user_service.chat_client.registered.lock().await
+                    && self.user_service.chat_client.group.lock().await.is_some()
+                {
+                    let user_service = self.user_service.clone();
+                    tokio::spawn(async move {
+                        user_service.executor("/group users").await;
+                    });
+                }
+            }
+        }
+    }
+
+    /// Handles the mouse events and updates the state of [`App`].
+    pub async fn handle_mouse_events(&mut self, mouse_event: MouseEvent) {
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.tabs_area.contains(position) {
+                    if let Some(tab) = user_interface::tab_at(self.tabs_area, position.x) {
+                        self.select_tab(tab).await;
+                    }
+                    return;
+                }
                 match self.selected_tab {
-                    SelectedTab::Users => match key_event.code {
-                        KeyCode::Up => self.users_table.previous_row(),
-                        KeyCode::Down => self.users_table.next_row(),
-                        KeyCode::Char('u') => {
-                            if *self.user_service.chat_client.registered.lock().await {
-                                let user_service = self.user_service.clone();
-                                tokio::spawn(async move {
-                                    user_service.executor("/users").await;
-                                });
-                            }
-                        }
-                        KeyCode::Char('g') => {
-                            if *self.user_service.chat_client.registered.lock().await
-                                && self.user_service.chat_client.group.lock().await.is_some()
-                            {
-                                let user_service = self.user_service.clone();
-                                tokio::spawn(async move {
-                                    user_service.executor("/group users").await;
-                                });
-                            }
+                    SelectedTab::Chat => self.open_private_chat_at(position),
+                    SelectedTab::Mentions => {
+                        if let Some(i) = self.mentions_table.row_at(position) {
+                            self.mentions_table.select_row(i);
                         }
-                        _ => {
-                            self.text_input.input(key_event);
-                        }
-                    },
-                    SelectedTab::Help => match key_event.code {
-                        KeyCode::Up => self.help_table.previous_row(),
-                        KeyCode::Down => self.help_table.next_row(),
-                        _ => {}
-                    },
-                    SelectedTab::Chat => {
-                        if key_event.modifiers == KeyModifiers::SHIFT {
-                            match key_event.code {
-                                KeyCode::Left | KeyCode::Right => {
-                                    let str = self.search_input_history(key_event.code);
-                                    self.text_input = TextArea::default();
-                                    self.text_input.insert_str(str);
-                                }
-                                _ => {}
-                            }
+                    }
+                    SelectedTab::Users => {
+                        if let Some(i) = self.users_table.row_at(position) {
+                            self.users_table.select_row(i);
                         }
-
-                        match key_event.code {
-                            KeyCode::Down => {
-                                self.vertical_scroll = self.vertical_scroll.saturating_add(1);
-                                self.vertical_scroll_state =
-                                    self.vertical_scroll_state.position(self.vertical_scroll);
-                            }
-                            KeyCode::Up => {
-                                self.vertical_scroll = self.vertical_scroll.saturating_sub(1);
-                                self.vertical_scroll_state =
-                                    self.vertical_scroll_state.position(self.vertical_scroll);
-                            }
-                            KeyCode::Enter => self.events.send(AppEvent::Enter),
-                            // Other handlers you could add here.
-                            _ => {
-                                self.text_input.input(key_event);
-                            }
+                    }
+                    SelectedTab::Help => {
+                        if let Some(i) = self.help_table.row_at(position) {
+                            self.help_table.select_row(i);
                         }
                     }
-                    _ => {}
-                };
+                }
             }
+            MouseEventKind::ScrollUp => match self.selected_tab {
+                SelectedTab::Chat => self.scroll_by(-MOUSE_SCROLL_LINES),
+                _ => self.handle_action(Action::RowUp).await,
+            },
+            MouseEventKind::ScrollDown => match self.selected_tab {
+                SelectedTab::Chat => self.scroll_by(MOUSE_SCROLL_LINES),
+                _ => self.handle_action(Action::RowDown).await,
+            },
+            _ => {}
+        }
+    }
+
+    // a click on the name of a sender prepares a private message to that client
+    fn open_private_chat_at(&mut self, position: Position) {
+        let inner = self.chat_area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        if !inner.contains(position) {
+            return;
+        }
+
+        let row = self.vertical_scroll + usize::from(position.y - inner.y);
+        // only the first row of a message contains the name
+        let Some((index, 0)) = self.messages.line_at_row(row) else {
+            return;
+        };
+        let Some(line) = self.messages.get(index) else {
+            return;
+        };
+        // the badge and the name are in front of the colon
+        let sender: Vec<&Span> = line
+            .spans
+            .iter()
+            .take_while(|span| span.content != ": ")
+            .collect();
+        if usize::from(position.x - inner.x) >= sender.iter().map(|s| s.width()).sum() {
+            return;
+        }
+
+        if let Some(client_id) = sender
+            .iter()
+            .find_map(|span| self.known_clients.get(span.content.as_ref()))
+        {
+            self.text_input = TextArea::default();
+            self.text_input.insert_str(format!("/private {client_id} "));
         }
-        Ok(())
     }
 
     /// Handles the tick event of the terminal.
     ///
     /// The tick event is where you can update the state of your application with any logic that
     /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
-    pub fn tick(&self) {}
+    /// Returns whether the UI has to be redrawn.
+    pub fn tick(&mut self) -> bool {
+        // private messages count as read once the user looks at the end of the chat
+        if matches!(self.selected_tab, SelectedTab::Chat) && self.follow_tail {
+            for (client_id, id) in std::mem::take(&mut self.unread_private) {
+                self.send_receipt(&client_id, DeliveryStatus::Read, &id);
+            }
+        }
+        self.typing.expire()
+    }
 
     /// Set running to false to quit the application.
     pub async fn quit(&mut self) {
@@ -235,14 +495,19 @@ impl<'a> App<'a> {
         }
     }
 
-    pub async fn evaluate_response(&mut self, rsp: Response) -> Option<Vec<Line<'static>>> {
+    pub async fn evaluate_response(&mut self, rsp: Response) -> Option<Vec<Render>> {
         match rsp {
             // error output
             Response { err, .. } if !err.is_empty() => {
                 if err == IGNORE_RESPONSE_TAG {
                     return None;
                 }
-                Some(vec![Line::from(red_span(err.to_string()))])
+                Some(vec![Render::text(move || error_span(err.clone()))])
+            }
+
+            // theme output, an empty theme name lists the themes
+            Response { rsp_name, .. } if rsp_name == THEME_FLAG => {
+                Some(self.switch_theme(&rsp.content))
             }
 
             // empty output
@@ -258,11 +523,7 @@ impl<'a> App<'a> {
                     .await
                     .clone();
                 self.switch_title(REGISTER_FLAG, [client_name, String::from("")]);
-                Some(vec![
-                    Line::from(blue_span(REGISTER_OUTPUT.to_string())),
-                    Line::from(blue_span(REGISTER_HELP_OUTPUT.to_string())),
-                    Line::from(blue_span(REGISTER_QUIT_OUTPUT.to_string())),
-                ])
+                Some(register_output())
             }
 
             // server output
@@ -270,29 +531,41 @@ impl<'a> App<'a> {
                 // unregister output
                 if rsp.content == UNREGISTER_FLAG {
                     self.switch_title(UNREGISTER_FLAG, [String::new(), String::new()]);
-                    self.display_message(vec![Line::from(blue_span(DEFAULT_MESSAGE.to_string()))]);
+                    self.display_message(vec![Render::text(|| info_span(tr("default-message")))]);
                     self.users_table.update_items(
                         vec![],
                         self.user_service.chat_client.own_json_client().await,
                     );
+                    return Some(vec![Render::text(|| info_span(tr("unregistered")))]);
                 }
 
-                Some(vec![Line::from(blue_span(rsp.content))])
+                let content = rsp.content;
+                Some(vec![Render::text(move || info_span(content.clone()))])
             }
 
             // one user left output
             Response { rsp_name, .. } if rsp_name == USER_REMOVE_FLAG => {
-                Some(vec![Line::from(vec![
-                    purple_span(rsp.content),
-                    blue_span(String::from(" hat den Chat verlassen")),
-                ])])
+                let name = rsp.content;
+                Some(vec![Render::text(move || {
+                    translated_line(
+                        "user-left",
+                        vec![("name", accent_span(name.clone()))],
+                        info_span,
+                    )
+                })])
             }
 
             // one user joined output
-            Response { rsp_name, .. } if rsp_name == USER_ADD_FLAG => Some(vec![Line::from(vec![
-                purple_span(rsp.content),
-                blue_span(String::from(" ist dem Chat beigetreten")),
-            ])]),
+            Response { rsp_name, .. } if rsp_name == USER_ADD_FLAG => {
+                let name = rsp.content;
+                Some(vec![Render::text(move || {
+                    translated_line(
+                        "user-joined",
+                        vec![("name", accent_span(name.clone()))],
+                        info_span,
+                    )
+                })])
+            }
 
             // add group output
             Response { rsp_name, .. } if rsp_name == ADD_GROUP_FLAG => {
@@ -304,7 +577,8 @@ impl<'a> App<'a> {
                 {
                     Result::Ok(g) => g.name,
                     Result::Err(e) => {
-                        return Some(vec![Line::from(red_span(format!("{}: {}", e.kind, e.msg)))]);
+                        let error = format!("{}: {}", e.kind, e.msg);
+                        return Some(vec![Render::text(move || error_span(error.clone()))]);
                     }
                 };
                 let client_name = self
@@ -316,14 +590,14 @@ impl<'a> App<'a> {
                     .clone();
                 self.switch_title(ADD_GROUP_FLAG, [client_name, group_name.clone()]);
                 Some(vec![
-                    Line::from(vec![
-                        blue_span("-> Du bist nun Teil der Gruppe ".to_string()),
-                        turkis_span(group_name),
-                    ]),
-                    Line::from(blue_span(
-                        " [ Private Nachrichten kannst du weiterhin außerhalb verschicken ]"
-                            .to_string(),
-                    )),
+                    Render::text(move || {
+                        translated_line(
+                            "group-joined",
+                            vec![("group", name_span(group_name.clone()))],
+                            info_span,
+                        )
+                    }),
+                    Render::text(|| info_span(tr("group-private-hint"))),
                 ])
             }
 
@@ -338,16 +612,17 @@ impl<'a> App<'a> {
                     .await
                     .clone();
                 self.switch_title(REGISTER_FLAG, [client_name, String::new()]);
-                Some(vec![
-                    Line::from(blue_span(REGISTER_OUTPUT.to_string())),
-                    Line::from(blue_span(REGISTER_HELP_OUTPUT.to_string())),
-                    Line::from(blue_span(REGISTER_QUIT_OUTPUT.to_string())),
-                ])
+                Some(register_output())
             }
 
             // users output
             Response { rsp_name, .. } if rsp_name == USERS_FLAG => {
                 let users: Vec<JsonClient> = serde_json::from_str(&rsp.content).unwrap_or_default();
+                for user in users.iter() {
+                    self.known_clients
+                        .insert(user.name.clone(), user.client_id.clone());
+                }
+                *self.user_service.chat_client.users.lock().await = users.clone();
                 self.users_table
                     .update_items(users, self.user_service.chat_client.own_json_client().await);
 
@@ -362,24 +637,291 @@ impl<'a> App<'a> {
                 None
             }
 
+            // state of an own private message
+            Response { rsp_name, .. } if rsp_name == DELIVERY_FLAG => {
+                let update: DeliveryUpdate = serde_json::from_str(&rsp.content).ok()?;
+                if update.status == DeliveryStatus::Sending {
+                    let mut sent = SentPrivate {
+                        line_id: 0,
+                        receiver: self.client_name(&rsp.client_id),
+                        receiver_id: rsp.client_id,
+                        text: update.text,
+                        status: update.status,
+                        edited: false,
+                    };
+                    let mut lines: Vec<Render> = update.reply_to.into_iter().map(quote).collect();
+                    lines.push(sent.render(self.name_badges));
+                    self.display_message(lines);
+                    sent.line_id = self.messages.last_id();
+                    self.sent_private.insert(update.id, sent);
+                    self.forget_dropped_lines();
+                } else {
+                    self.update_delivery(&update.id, update.status);
+                }
+                None
+            }
+
+            // state of an own message in the outbox, waiting ones are greyed out
+            Response { rsp_name, .. } if rsp_name == OUTBOX_FLAG => {
+                let update: OutboxUpdate = serde_json::from_str(&rsp.content).ok()?;
+                let msg = update.message;
+                if msg.receiver.is_empty() {
+                    let name = self
+                        .user_service
+                        .chat_client
+                        .client_name
+                        .lock()
+                        .await
+                        .clone();
+                    let client_id = self.user_service.chat_client.client_id.lock().await.clone();
+                    self.update_queued(update.state, &msg, name, &client_id);
+                } else {
+                    self.update_queued_private(update.state, &msg);
+                }
+                let error = msg.error.filter(|_| update.state == OutboxState::Failed)?;
+                let error = tr_args(
+                    "outbox-failed",
+                    &[
+                        ("id", msg.id.to_string()),
+                        ("text", msg.text),
+                        ("error", error),
+                    ],
+                );
+                Some(vec![Render::text(move || error_span(error.clone()))])
+            }
+
+            // edit or deletion of an own private message
+            Response { rsp_name, .. } if rsp_name == EDIT_FLAG => {
+                match edits::parse(&rsp.content)? {
+                    ChatEdit::Edit { id, text } => {
+                        let badges = self.name_badges;
+                        let sent = self.sent_private.get_mut(id)?;
+                        sent.text = text.to_string();
+                        sent.edited = true;
+                        self.messages.replace(sent.line_id, sent.render(badges));
+                    }
+                    ChatEdit::Delete { id } => {
+                        let sent = self.sent_private.remove(id)?;
+                        let (line_id, badges) = (sent.line_id, self.name_badges);
+                        let line = Render::text(move || {
+                            let mut receiver = vec![Span::from("→ ")];
+                            receiver.extend(sent.spans(badges));
+                            deleted_line(receiver)
+                        });
+                        self.messages.replace(line_id, line);
+                        self.messages.set_footer(line_id, Render::new(Vec::new));
+                    }
+                }
+                None
+            }
+
+            // edit or deletion of a received message, only its sender may change it
+            Response { content, .. } if edits::parse(&content).is_some() => {
+                let edit = edits::parse(&content)?;
+                let (ChatEdit::Edit { id, .. } | ChatEdit::Delete { id }) = edit;
+                let chat_line = self
+                    .chat_lines
+                    .get(id)
+                    .filter(|l| l.client_id == rsp.client_id)?;
+                let own_name = self
+                    .user_service
+                    .chat_client
+                    .client_name
+                    .lock()
+                    .await
+                    .clone();
+                let (name, badges) = (chat_line.name.clone(), self.name_badges);
+                match edit {
+                    ChatEdit::Edit { text, .. } => {
+                        let line = message(
+                            name,
+                            rsp.client_id,
+                            badges,
+                            text.to_string(),
+                            true,
+                            own_name,
+                        );
+                        self.messages.replace(chat_line.line_id, line);
+                        if let Some(chat_line) = self.chat_lines.get_mut(id) {
+                            chat_line.text = text.to_string();
+                        }
+                    }
+                    ChatEdit::Delete { id } => {
+                        let client_id = rsp.client_id;
+                        let line = Render::text(move || {
+                            deleted_line(sender_spans(name.clone(), &client_id, badges))
+                        });
+                        self.messages.replace(chat_line.line_id, line);
+                        self.messages
+                            .set_footer(chat_line.line_id, Render::new(Vec::new));
+                        self.chat_lines.remove(id);
+                    }
+                }
+                None
+            }
+
+            // reaction to a message, own reactions to private messages come with the REACTION_FLAG
+            Response { content, .. } if reactions::parse(&content).is_some() => {
+                let reaction = reactions::parse(&content)?;
+                let line_id = self.line_of(reaction.id)?;
+                if self.reactions.apply(&rsp.client_id, reaction) {
+                    let own_id = self.user_service.chat_client.client_id.lock().await.clone();
+                    let counts = self.reactions.counts(reaction.id, &own_id);
+                    self.messages
+                        .set_footer(line_id, Render::new(move || reaction_footer(&counts)));
+                }
+                None
+            }
+
+            // receipt of the receiver of an own private message
+            Response { content, .. } if receipts::parse_receipt(&content).is_some() => {
+                if let Some((status, id)) = receipts::parse_receipt(&content) {
+                    self.update_delivery(id, status);
+                }
+                None
+            }
+
+            // incoming call, the calls themselves are not supported yet
+            Response { rsp_name, .. } if rsp_name == RECEIVE_CALL => {
+                let caller = self
+                    .known_clients
+                    .iter()
+                    .find(|(_, id)| **id == rsp.client_id)
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or(rsp.client_id);
+                self.notifier.notify(Notification {
+                    kind: NotificationKind::Call,
+                    name: caller.clone(),
+                    text: tr("call-incoming-text"),
+                    place: tr("mention-private"),
+                });
+                Some(vec![Render::text(move || {
+                    translated_line(
+                        "call-incoming",
+                        vec![("name", name_span(caller.clone()))],
+                        info_span,
+                    )
+                })])
+            }
+
+            // typing notification, the own ones come back from the group
+            Response { content, .. } if content == TYPING_FLAG => {
+                if rsp.client_id != *self.user_service.chat_client.client_id.lock().await {
+                    self.typing.received(&rsp.rsp_name);
+                }
+                None
+            }
+
             // response output
             _ => {
-                if rsp.content.starts_with("[") {
+                // control messages of newer clients
+                if control::is_control(&rsp.content) {
                     return None;
                 }
-                let result = vec![Line::from(vec![
-                    turkis_span(rsp.rsp_name),
-                    Span::from(": "),
-                    Span::from(rsp.content),
-                ])];
-                Some(result)
+                self.typing.stopped(&rsp.rsp_name);
+                let (text, id) = receipts::split_id(&rsp.content);
+                // only private messages get receipts
+                if let Some(id) = id
+                    && !rsp.client_id.is_empty()
+                    && receipts::wants_receipt(&rsp.content)
+                {
+                    if matches!(self.selected_tab, SelectedTab::Chat) && self.follow_tail {
+                        self.send_receipt(&rsp.client_id, DeliveryStatus::Read, id);
+                    } else {
+                        self.send_receipt(&rsp.client_id, DeliveryStatus::Delivered, id);
+                        self.unread_private
+                            .push((rsp.client_id.clone(), id.to_string()));
+                    }
+                }
+                // replies are shown below a quote of their parent
+                let (reply_to, text) = replies::split_reply(text);
+                let text = text.to_string();
+                let private = receipts::wants_receipt(&rsp.content);
+                if !rsp.client_id.is_empty() {
+                    self.known_clients
+                        .insert(rsp.rsp_name.clone(), rsp.client_id.clone());
+                }
+                let own_name = self
+                    .user_service
+                    .chat_client
+                    .client_name
+                    .lock()
+                    .await
+                    .clone();
+                let mut lines: Vec<Render> = reply_to.into_iter().map(quote).collect();
+                lines.push(message(
+                    rsp.rsp_name.clone(),
+                    rsp.client_id.clone(),
+                    self.name_badges,
+                    text.clone(),
+                    false,
+                    own_name.clone(),
+                ));
+                // the line id is needed for later edits and the mentions
+                self.display_message(lines);
+                let id = id.filter(|_| !rsp.client_id.is_empty()).unwrap_or_default();
+                let from_other = !rsp.client_id.is_empty()
+                    && rsp.client_id != *self.user_service.chat_client.client_id.lock().await;
+                let mentioned = from_other && mentions::mentions(&text, &own_name);
+                let place = match &*self.user_service.chat_client.group.lock().await {
+                    _ if private => tr("mention-private"),
+                    Some(group) => group.name.clone(),
+                    None => tr("mention-everyone"),
+                };
+                if mentioned {
+                    self.mentions_table.push(MentionItem {
+                        name: rsp.rsp_name.clone(),
+                        place: place.clone(),
+                        text: text.clone(),
+                        line_id: self.messages.last_id(),
+                        message_id: id.to_string(),
+                    });
+                    if matches!(self.selected_tab, SelectedTab::Mentions) {
+                        self.mentions_table.unread = 0;
+                    }
+                }
+                if from_other && (private || mentioned) {
+                    self.notifier.notify(Notification {
+                        kind: if private {
+                            NotificationKind::Private
+                        } else {
+                            NotificationKind::Mention
+                        },
+                        name: rsp.rsp_name.clone(),
+                        text: text.clone(),
+                        place,
+                    });
+                }
+                if id.is_empty() {
+                    return None;
+                }
+                let id = id.to_string();
+                self.chat_lines.insert(
+                    id,
+                    ChatLine {
+                        line_id: self.messages.last_id(),
+                        client_id: rsp.client_id,
+                        name: rsp.rsp_name,
+                        text,
+                        private,
+                    },
+                );
+                self.forget_dropped_lines();
+                None
             }
         }
     }
 
-    pub fn display_message(&mut self, lines: Vec<Line<'a>>) {
+    pub fn display_message(&mut self, lines: Vec<Render>) {
+        if !self.follow_tail {
+            self.unseen_messages += lines.len();
+        }
         for line in lines {
-            self.messages.lines.push(line);
+            let dropped_rows = self.messages.push(line);
+            // keeps the view on the same messages while the user is scrolled up
+            if !self.follow_tail {
+                self.vertical_scroll = self.vertical_scroll.saturating_sub(dropped_rows);
+            }
         }
     }
 
@@ -387,58 +929,498 @@ impl<'a> App<'a> {
         let input = self.text_input.lines().join("\n");
         let input_clone = input.clone();
         let user_service = self.user_service.clone();
-        tokio::spawn(async move {
-            user_service.executor(input.as_str()).await;
-        });
+        // commands don't answer the message, the reply waits for the next chat message
+        let is_chat =
+            !input.starts_with('/') || input.starts_with("/private ") || input.starts_with("/msg ");
+        match self.replying.take_if(|_| is_chat) {
+            Some(reply_to) => {
+                self.selected = None;
+                tokio::spawn(async move {
+                    user_service.reply_executor(&input, reply_to).await;
+                });
+            }
+            None => {
+                tokio::spawn(async move {
+                    user_service.executor(input.as_str()).await;
+                });
+            }
+        }
         self.history.save_input(input_clone);
         self.text_input = TextArea::default();
-        self.scroll();
+        self.typing.message_sent();
+        self.scroll_to_bottom();
+    }
+
+    // pasted text is inserted as a whole, its line breaks don't send it
+    async fn paste(&mut self, text: &str) {
+        if !matches!(self.selected_tab, SelectedTab::Chat | SelectedTab::Users) {
+            return;
+        }
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        if self.text_input.insert_str(text) {
+            self.notify_typing().await;
+        }
+    }
+
+    // tells the receiver of a private message or the group that the user is typing
+    async fn notify_typing(&mut self) {
+        if !*self.user_service.chat_client.registered.lock().await {
+            return;
+        }
+        let input = self.text_input.lines().join("\n");
+        // the receiver is empty for the group
+        let (conversation, receiver) = if let Some(rest) = input
+            .strip_prefix("/private ")
+            .or_else(|| input.strip_prefix("/msg "))
+        {
+            match rest.split_once(' ') {
+                Some((client_id, text)) if !client_id.is_empty() && !text.trim().is_empty() => {
+                    (client_id.to_string(), client_id.to_string())
+                }
+                _ => return,
+            }
+        } else if !input.starts_with('/') && !input.trim().is_empty() {
+            match &*self.user_service.chat_client.group.lock().await {
+                Some(group) => (group.group_id.clone(), String::new()),
+                None => return,
+            }
+        } else {
+            return;
+        };
+
+        if self.typing.should_send(&conversation) {
+            self.send_control_in_background(TYPING_FLAG.to_string(), receiver);
+        }
+    }
+
+    fn send_receipt(&self, client_id: &str, status: DeliveryStatus, id: &str) {
+        if let Some(receipt) = receipts::receipt(status, id) {
+            self.send_control_in_background(receipt, client_id.to_string());
+        }
+    }
+
+    // for notifications, which show nothing in the chat
+    fn send_control_in_background(&self, content: String, receiver: String) {
+        let chat_client = self.user_service.chat_client.clone();
+        tokio::spawn(async move {
+            let _ = chat_client.send_control(&content, &receiver).await;
+        });
+    }
+
+    // a status is only replaced by a later one, e.g. a late delivered receipt doesn't undo read
+    fn update_delivery(&mut self, id: &str, status: DeliveryStatus) {
+        let Some(sent) = self.sent_private.get_mut(id) else {
+            return;
+        };
+        if status_rank(status) <= status_rank(sent.status) {
+            return;
+        }
+        sent.status = status;
+        if !self
+            .messages
+            .replace(sent.line_id, sent.render(self.name_badges))
+        {
+            self.sent_private.remove(id);
+        }
+    }
+
+    // the lines of edited messages are kept while they are in the buffer
+    fn forget_dropped_lines(&mut self) {
+        let capacity = self.messages.capacity();
+        let known = self.chat_lines.len() + self.sent_private.len();
+        if self.chat_lines.len() > capacity {
+            self.chat_lines
+                .retain(|_, l| self.messages.contains(l.line_id));
+        }
+        if self.sent_private.len() > capacity {
+            self.sent_private
+                .retain(|_, s| self.messages.contains(s.line_id));
+        }
+        if self.chat_lines.len() + self.sent_private.len() < known {
+            let (chat_lines, sent_private) = (&self.chat_lines, &self.sent_private);
+            self.reactions
+                .retain(|id| chat_lines.contains_key(id) || sent_private.contains_key(id));
+        }
+    }
+
+    // line of a message with an id in the chat
+    fn line_of(&self, message_id: &str) -> Option<usize> {
+        self.chat_lines
+            .get(message_id)
+            .map(|l| l.line_id)
+            .or_else(|| self.sent_private.get(message_id).map(|s| s.line_id))
+            .filter(|line_id| self.messages.contains(*line_id))
+    }
+
+    // line of the selected message, it is highlighted in the chat
+    pub fn selected_line(&self) -> Option<usize> {
+        self.line_of(self.selected.as_deref()?)
+    }
+
+    // message id of the selected message and the client id to which a reaction
+    // is sent, which is empty for group messages
+    fn selected_target(&self) -> Option<(String, String)> {
+        let id = self.selected.clone()?;
+        self.selected_line()?;
+        if let Some(sent) = self.sent_private.get(&id) {
+            let receiver = sent.receiver_id.clone();
+            return Some((id, receiver));
+        }
+        let chat_line = self.chat_lines.get(&id)?;
+        let receiver = if chat_line.private {
+            chat_line.client_id.clone()
+        } else {
+            String::new()
+        };
+        Some((id, receiver))
+    }
+
+    // moves the selection between the messages with ids, selecting past the newest
+    // message clears the selection
+    fn select_message(&mut self, forward: bool) {
+        let mut lines: Vec<(usize, &String)> = self
+            .chat_lines
+            .iter()
+            .map(|(id, l)| (l.line_id, id))
+            .chain(self.sent_private.iter().map(|(id, s)| (s.line_id, id)))
+            .filter(|(line_id, _)| self.messages.contains(*line_id))
+            .collect();
+        lines.sort();
+
+        let next = match (self.selected_line(), forward) {
+            (None, true) => None,
+            (None, false) => lines.last(),
+            (Some(current), true) => lines.iter().find(|(l, _)| *l > current),
+            // the oldest message stays selected
+            (Some(current), false) => lines
+                .iter()
+                .rev()
+                .find(|(l, _)| *l < current)
+                .or_else(|| lines.iter().find(|(l, _)| *l == current)),
+        };
+        let next = next.map(|(line_id, id)| (*line_id, (*id).clone()));
+        match next {
+            Some((line_id, id)) => {
+                self.selected = Some(id);
+                self.scroll_to_line(line_id);
+            }
+            None => self.selected = None,
+        }
+    }
+
+    // scrolls just enough to show the whole line
+    fn scroll_to_line(&mut self, line_id: usize) {
+        let Some((top, height)) = self.messages.rows_of(line_id) else {
+            return;
+        };
+        let view_height = usize::from(self.chat_inner_area().height);
+        if top < self.vertical_scroll || height > view_height {
+            self.vertical_scroll = top;
+        } else if top + height > self.vertical_scroll + view_height {
+            self.vertical_scroll = top + height - view_height;
+        }
+        self.follow_tail = false;
+        self.scroll_by(0);
     }
 
-    pub fn scroll(&mut self) {
-        if self.messages.lines.len() > self.chat_size.height.saturating_sub(5).into() {
-            self.vertical_scroll = self
-                .messages
-                .lines
-                .len()
-                .saturating_sub(self.chat_size.height.into());
-            self.vertical_scroll_state = self.vertical_scroll_state.position(self.vertical_scroll);
+    // the next chat message answers the selected one, private messages are
+    // answered privately
+    async fn reply_to_selected(&mut self) {
+        let Some((id, receiver)) = self.selected_target() else {
+            return;
+        };
+        let reply_to = match self.chat_lines.get(&id) {
+            Some(chat_line) => ReplyTo::new(&id, &chat_line.name, &chat_line.text),
+            None => {
+                let Some(sent) = self.sent_private.get(&id) else {
+                    return;
+                };
+                let name = self
+                    .user_service
+                    .chat_client
+                    .client_name
+                    .lock()
+                    .await
+                    .clone();
+                ReplyTo::new(&id, &name, &sent.text)
+            }
+        };
+        self.replying = Some(reply_to);
+
+        let input = self.text_input.lines().join("\n");
+        let prefix = format!("/private {receiver} ");
+        if !receiver.is_empty() && !input.starts_with(&prefix) {
+            let text = if input.starts_with('/') { "" } else { &input };
+            self.text_input = TextArea::default();
+            self.text_input.insert_str(format!("{prefix}{text}"));
         }
     }
 
+    // shows the message of the selected mention in the chat
+    async fn open_mention(&mut self) {
+        let Some(mention) = self.mentions_table.selected() else {
+            return;
+        };
+        let (line_id, id) = (mention.line_id, mention.message_id.clone());
+        self.select_tab(SelectedTab::Chat).await;
+        if !self.messages.contains(line_id) {
+            return;
+        }
+        if self.line_of(&id) == Some(line_id) {
+            self.selected = Some(id);
+        }
+        self.scroll_to_line(line_id);
+    }
+
+    // replaces the @name in front of the cursor with the next known name which
+    // starts with it, returns false if there is nothing to complete
+    async fn complete_mention(&mut self) -> bool {
+        let (row, col) = self.text_input.cursor();
+        let Some(line) = self.text_input.lines().get(row) else {
+            return false;
+        };
+        let before: String = line.chars().take(col).collect();
+        let word = before
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default();
+        let Some(typed) = word.strip_prefix('@') else {
+            return false;
+        };
+
+        let (partial, index) = match self.completion.take() {
+            Some((partial, index)) => (partial, index + 1),
+            None => (typed.to_string(), 0),
+        };
+        let own_name = self
+            .user_service
+            .chat_client
+            .client_name
+            .lock()
+            .await
+            .clone();
+        let names: Vec<&str> = self
+            .known_clients
+            .keys()
+            .map(String::as_str)
+            .filter(|name| *name != own_name)
+            .collect();
+        let matches = mentions::complete(&partial, &names);
+        if matches.is_empty() {
+            return false;
+        }
+        let name = matches[index % matches.len()].to_string();
+
+        for _ in 0..typed.chars().count() {
+            self.text_input.delete_char();
+        }
+        self.text_input.insert_str(name);
+        self.completion = Some((partial, index));
+        true
+    }
+
+    // sends the reaction chosen in the picker
+    fn react(&mut self) {
+        let Some(picker) = self.reaction_picker.take() else {
+            return;
+        };
+        let command = picker.command();
+        let user_service = self.user_service.clone();
+        tokio::spawn(async move {
+            user_service.executor(&command).await;
+        });
+    }
+
+    // a queued group message is shown greyed out until the server sends it back
+    fn update_queued(
+        &mut self,
+        state: OutboxState,
+        msg: &QueuedMessage,
+        name: String,
+        client_id: &str,
+    ) {
+        let line = match state {
+            OutboxState::Queued => {
+                let msg = msg.clone();
+                Render::text(move || queued_line(&name, &msg))
+            }
+            OutboxState::Failed => {
+                let (client_id, badges, text) =
+                    (client_id.to_string(), self.name_badges, msg.text.clone());
+                Render::new(move || {
+                    let mut sender = sender_spans(name.clone(), &client_id, badges);
+                    sender.push(Span::from(": "));
+                    let mut text = markdown::message_text(sender, &text, "");
+                    if let Some(last) = text.text.lines.last_mut() {
+                        last.push_span(Span::from(" "));
+                        last.push_span(error_span(String::from("✗")));
+                    }
+                    text
+                })
+            }
+            OutboxState::Sent | OutboxState::Cancelled => {
+                if let Some(line_id) = self.queued_lines.remove(&msg.id) {
+                    self.messages.remove(line_id);
+                }
+                return;
+            }
+        };
+        match self.queued_lines.get(&msg.id) {
+            Some(line_id) => {
+                self.messages.replace(*line_id, line);
+            }
+            None => {
+                self.display_message(vec![line]);
+                self.queued_lines.insert(msg.id, self.messages.last_id());
+            }
+        }
+    }
+
+    // a queued private message keeps its line, which is greyed out while it waits
+    fn update_queued_private(&mut self, state: OutboxState, msg: &QueuedMessage) {
+        let badges = self.name_badges;
+        let Some(sent) = self.sent_private.get_mut(&msg.message_id) else {
+            return;
+        };
+        let line = match state {
+            OutboxState::Queued => {
+                sent.status = DeliveryStatus::Sending;
+                sent.text = msg.text.clone();
+                let (sender, msg) = (format!("→ {}", sent.receiver), msg.clone());
+                Render::text(move || queued_line(&sender, &msg))
+            }
+            OutboxState::Sent => sent.render(badges),
+            OutboxState::Failed => {
+                sent.status = DeliveryStatus::Failed;
+                sent.render(badges)
+            }
+            OutboxState::Cancelled => {
+                if let Some(sent) = self.sent_private.remove(&msg.message_id) {
+                    self.messages.remove(sent.line_id);
+                }
+                return;
+            }
+        };
+        if !self.messages.replace(sent.line_id, line) {
+            self.sent_private.remove(&msg.message_id);
+        }
+    }
+
+    // name of a known client, otherwise its id
+    fn client_name(&self, client_id: &str) -> String {
+        self.known_clients
+            .iter()
+            .find(|(_, id)| *id == client_id)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| client_id.to_string())
+    }
+
+    // area inside the border of the message block
+    pub fn chat_inner_area(&self) -> Rect {
+        self.chat_area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        })
+    }
+
+    // highest scroll offset, at which the last wrapped row is at the bottom of the chat
+    pub fn max_scroll(&self) -> usize {
+        let inner = self.chat_inner_area();
+        self.messages
+            .total_height()
+            .saturating_sub(inner.height.into())
+    }
+
+    fn page_height(&self) -> isize {
+        (self.chat_inner_area().height as isize - 1).max(1)
+    }
+
+    // scrolls by wrapped rows, reaching the bottom enables follow tail again
+    pub fn scroll_by(&mut self, rows: isize) {
+        let max_scroll = self.max_scroll();
+        self.vertical_scroll = self
+            .vertical_scroll
+            .saturating_add_signed(rows)
+            .min(max_scroll);
+        self.follow_tail = self.vertical_scroll >= max_scroll;
+        if self.follow_tail {
+            self.unseen_messages = 0;
+        }
+        self.vertical_scroll_state = self.vertical_scroll_state.position(self.vertical_scroll);
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.follow_tail = true;
+        self.scroll_by(isize::MAX);
+    }
+
     pub fn switch_title(&mut self, title: &str, strings: [String; 2]) {
         match title {
-            UNREGISTER_FLAG => self.title = Line::from(DEFAULT_TITLE),
+            UNREGISTER_FLAG => self.title = Line::from(tr("default-title")),
             REGISTER_FLAG => {
-                self.title = Line::from(vec![
-                    Span::raw("Du bist registriert "),
-                    purple_span(strings[0].clone()),
-                    Span::raw("!"),
-                ]);
+                self.title = translated_line(
+                    "title-registered",
+                    vec![("name", accent_span(strings[0].clone()))],
+                    Span::raw,
+                );
             }
             ADD_GROUP_FLAG => {
-                self.title = Line::from(vec![
-                    purple_span(strings[0].clone()),
-                    Span::raw(", du bist in der Gruppe "),
-                    purple_span(strings[1].clone()),
-                    Span::raw("!"),
-                ])
+                self.title = translated_line(
+                    "title-group",
+                    vec![
+                        ("name", accent_span(strings[0].clone())),
+                        ("group", accent_span(strings[1].clone())),
+                    ],
+                    Span::raw,
+                )
             }
-            _ => self.title = Line::from(DEFAULT_TITLE),
+            _ => self.title = Line::from(tr("default-title")),
         }
     }
 
-    pub fn search_input_history(&mut self, key_code: KeyCode) -> String {
+    pub fn switch_theme(&mut self, name: &str) -> Vec<Render> {
+        if name.is_empty() {
+            return vec![Render::text(|| {
+                translated_line(
+                    "theme-list",
+                    vec![("themes", name_span(theme::available().join(", ")))],
+                    info_span,
+                )
+            })];
+        }
+
+        let new_theme = match Theme::load(name) {
+            Result::Ok(t) => t,
+            Result::Err(e) => {
+                let error = e.to_string();
+                return vec![Render::text(move || error_span(error.clone()))];
+            }
+        };
+        theme::set(new_theme);
+        // the lines take the colors of the theme when they are built
+        self.messages.rebuild();
+
+        let name = name.to_string();
+        vec![Render::text(move || {
+            translated_line(
+                "theme-switched",
+                vec![("theme", name_span(name.clone()))],
+                info_span,
+            )
+        })]
+    }
+
+    pub fn search_input_history(&mut self, action: Action) -> String {
         let pending: i32;
-        if self.messages.lines.is_empty() || self.history.inputs.is_empty() {
+        if self.messages.is_empty() || self.history.inputs.is_empty() {
             return String::new();
         }
 
         let first = self.history.check_first();
 
-        match key_code {
-            KeyCode::Right => pending = self.history.current + 1,
-            KeyCode::Left => {
+        match action {
+            Action::HistoryNext => pending = self.history.current + 1,
+            Action::HistoryPrevious => {
                 if !first {
                     pending = self.history.current - 1;
                 } else {
@@ -452,12 +1434,13 @@ impl<'a> App<'a> {
         self.history.inputs[current].clone()
     }
 
-    pub fn next_tab(&mut self) {
-        self.selected_tab = self.selected_tab.next();
-    }
-
-    pub fn previous_tab(&mut self) {
-        self.selected_tab = self.selected_tab.previous();
+    pub async fn select_tab(&mut self, tab: SelectedTab) {
+        self.selected_tab = tab;
+        if matches!(tab, SelectedTab::Mentions) {
+            self.mentions_table.unread = 0;
+        }
+        self.update_users_tab().await;
+        self.update_help_tab().await;
     }
 
     pub async fn update_users_tab(&self) {
@@ -476,7 +1459,11 @@ impl<'a> App<'a> {
         }
     }
 
-    pub async fn update_help_tab(&self) {
+    pub async fn update_help_tab(&mut self) {
+        // scripts may have been reloaded
+        self.help_table
+            .set_local_items(local_help(&self.user_service));
+
         if self.help_table.filled() || !*self.user_service.chat_client.registered.lock().await {
             return;
         }
@@ -490,3 +1477,203 @@ impl<'a> App<'a> {
         });
     }
 }
+
+// a chat message, built again with the colors of the next theme
+fn message(
+    name: String,
+    client_id: String,
+    badge: bool,
+    text: String,
+    edited: bool,
+    own_name: String,
+) -> Render {
+    Render::new(move || {
+        let sender = sender_spans(name.clone(), &client_id, badge);
+        message_line(sender, &text, edited, &own_name)
+    })
+}
+
+// a chat message, edited ones are marked
+fn message_line(
+    mut sender: Vec<Span<'static>>,
+    text: &str,
+    edited: bool,
+    own_name: &str,
+) -> ChatText {
+    sender.push(Span::from(": "));
+    let mut text = markdown::message_text(sender, text, own_name);
+    if edited && let Some(last) = text.text.lines.last_mut() {
+        last.push_span(Span::from(" "));
+        last.push_span(dim_span(tr("message-edited")));
+    }
+    text
+}
+
+// the quote of the parent above a reply
+fn quote_line(reply_to: &ReplyTo) -> Line<'static> {
+    Line::from(dim_span(format!(
+        "{INDENT}╭ {}: {}",
+        reply_to.name, reply_to.excerpt
+    )))
+}
+
+fn quote(reply_to: ReplyTo) -> Render {
+    Render::text(move || quote_line(&reply_to))
+}
+
+// a deleted message keeps its place in the chat
+fn deleted_line(mut sender: Vec<Span<'static>>) -> Line<'static> {
+    sender.push(Span::from(": "));
+    sender.push(dim_span(tr("message-deleted")));
+    Line::from(sender)
+}
+
+// an own private message with its delivery status
+fn private_line(
+    receiver: Vec<Span<'static>>,
+    text: &str,
+    status: DeliveryStatus,
+    edited: bool,
+) -> ChatText {
+    let glyph = match status {
+        DeliveryStatus::Sending => info_span(String::from("○")),
+        DeliveryStatus::Delivered => info_span(String::from("✓")),
+        DeliveryStatus::Read => success_span(String::from("✓✓")),
+        DeliveryStatus::Failed => error_span(String::from("✗")),
+    };
+    let mut prefix = vec![Span::from("→ ")];
+    prefix.extend(receiver);
+    prefix.push(Span::from(": "));
+    let mut text = markdown::message_text(prefix, text, "");
+    if let Some(last) = text.text.lines.last_mut() {
+        last.push_span(Span::from(" "));
+        if edited {
+            last.push_span(dim_span(tr("message-edited")));
+            last.push_span(Span::from(" "));
+        }
+        last.push_span(glyph);
+    }
+    text
+}
+
+// an own message which waits in the outbox, with the number to cancel it
+fn queued_line(sender: &str, msg: &QueuedMessage) -> Text<'static> {
+    let text = format!("{sender}: {} ⏳ #{}", msg.text, msg.id);
+    let lines: Vec<Line<'static>> = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => Line::from(dim_span(line.to_string())),
+            _ => Line::from(dim_span(format!("{INDENT}{line}"))),
+        })
+        .collect();
+    Text::from(lines)
+}
+
+fn status_rank(status: DeliveryStatus) -> u8 {
+    match status {
+        DeliveryStatus::Sending => 0,
+        DeliveryStatus::Delivered => 1,
+        DeliveryStatus::Read => 2,
+        DeliveryStatus::Failed => 3,
+    }
+}
+
+fn local_help(user_service: &UserService) -> Vec<HelpItem> {
+    user_service
+        .plugin_registry
+        .help()
+        .into_iter()
+        .map(HelpItem::from)
+        .collect()
+}
+
+fn register_output() -> Vec<Render> {
+    [
+        "register-output",
+        "register-help-output",
+        "register-quit-output",
+    ]
+    .into_iter()
+    .map(|key| Render::text(move || info_span(tr(key))))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_chat_client::chat::chat_client::ChatClient;
+    use rust_chat_client::plugins::plugin_registry::PluginConfig;
+
+    // an app with a chat of 5 rows, the server is never reached
+    async fn app() -> App<'static> {
+        let (tx, rx) = tokio::sync::mpsc::channel(10);
+        let chat_client = ChatClient::new_client("http://127.0.0.1:9".to_string(), tx).await;
+        let user_service = Arc::new(UserService::new_user_service(
+            Arc::new(chat_client),
+            PluginConfig::default(),
+        ));
+        let settings = Settings::default();
+        let keymap = Keymap::from_settings(&settings).unwrap();
+        let mut app = App::new(user_service, rx, keymap, &settings);
+        app.chat_area = Rect::new(0, 0, 40, 7);
+        app.messages.set_width(app.chat_inner_area().width);
+        app
+    }
+
+    fn lines(count: usize) -> Vec<Render> {
+        (0..count)
+            .map(|i| Render::text(move || Text::raw(format!("line {i}"))))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn max_scroll_keeps_the_last_row_at_the_bottom() {
+        let mut app = app().await;
+        let rows = app.messages.total_height();
+        assert_eq!(app.max_scroll(), rows.saturating_sub(5));
+
+        app.display_message(lines(10));
+        assert_eq!(app.messages.total_height(), rows + 10);
+        assert_eq!(app.max_scroll(), rows + 10 - 5);
+    }
+
+    #[tokio::test]
+    async fn scrolling_stops_at_the_top_and_the_bottom() {
+        let mut app = app().await;
+        app.display_message(lines(20));
+        app.scroll_to_bottom();
+        let max_scroll = app.max_scroll();
+        assert_eq!(app.vertical_scroll, max_scroll);
+
+        app.scroll_by(-3);
+        assert_eq!(app.vertical_scroll, max_scroll - 3);
+        assert!(!app.follow_tail);
+
+        app.scroll_by(isize::MIN);
+        assert_eq!(app.vertical_scroll, 0);
+
+        // reaching the bottom follows the newest messages again
+        app.scroll_by(isize::MAX);
+        assert_eq!(app.vertical_scroll, max_scroll);
+        assert!(app.follow_tail);
+    }
+
+    #[tokio::test]
+    async fn messages_below_the_view_are_counted() {
+        let mut app = app().await;
+        app.display_message(lines(20));
+        app.scroll_to_bottom();
+        app.display_message(lines(2));
+        assert_eq!(app.unseen_messages, 0);
+
+        app.scroll_by(-1);
+        app.display_message(lines(3));
+        assert_eq!(app.unseen_messages, 3);
+
+        app.scroll_by(1);
+        assert_eq!(app.unseen_messages, 3);
+        app.scroll_to_bottom();
+        assert_eq!(app.unseen_messages, 0);
+    }
+}
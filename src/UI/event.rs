@@ -1,7 +1,7 @@
-use crate::types::Response;
 use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::Event as CrosstermEvent;
+use rust_chat_client::types::Response;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
@@ -0,0 +1,216 @@
+use std::io::{self, Write};
+
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::cursor::MoveTo;
+use ratatui::crossterm::queue;
+use ratatui::crossterm::style::{
+    Attribute, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Paragraph, Widget, Wrap};
+
+use crate::UI::message_buffer::Visible;
+
+// Links in the chat are written as OSC 8 hyperlinks, so the terminal can open them.
+// Ratatui can't render escape sequences, so the cells of a link are skipped when
+// the frame is drawn and the link is written to the terminal afterwards.
+
+#[derive(Debug, Clone)]
+pub struct Hyperlink {
+    pub x: u16,
+    pub y: u16,
+    pub text: String,
+    pub style: Style,
+    pub url: String,
+}
+
+// the style which marks the cells of the link with the index
+fn marker(index: usize) -> Style {
+    let [_, r, g, b] = (index as u32).to_be_bytes();
+    Style::new().fg(Color::Rgb(r, g, b))
+}
+
+fn link_index(style: Style) -> Option<usize> {
+    match style.fg {
+        Some(Color::Rgb(r, g, b)) => Some(u32::from_be_bytes([0, r, g, b]) as usize),
+        _ => None,
+    }
+}
+
+// finds the links in the rendered area and marks their cells to be skipped.
+// The lines are rendered again without styles, but with a marker on each link span,
+// so the wrapped parts of a link are found with the url recorded for its span.
+// The lines are wrapped and scrolled like the chat, cells in `covered` are left alone.
+pub fn take(
+    buf: &mut Buffer,
+    area: Rect,
+    visible: Visible,
+    covered: Option<Rect>,
+) -> Vec<Hyperlink> {
+    let mut lines = visible.lines;
+    for line in &mut lines {
+        line.style = Style::new();
+        for span in &mut line.spans {
+            span.style = Style::new();
+        }
+    }
+    for (index, link) in visible.links.iter().enumerate() {
+        if let Some(span) = lines
+            .get_mut(link.line)
+            .and_then(|line| line.spans.get_mut(link.span))
+        {
+            span.style = marker(index);
+        }
+    }
+    let mut markers = Buffer::empty(area);
+    Paragraph::new(lines)
+        .scroll((visible.skipped_rows as u16, 0))
+        .wrap(Wrap { trim: true })
+        .render(area, &mut markers);
+
+    let is_covered =
+        |x: u16, y: u16| covered.is_some_and(|rect| rect.contains(Position::new(x, y)));
+    let mut links = Vec::new();
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            let index = link_index(markers[(x, y)].style());
+            let Some(link) = index.and_then(|index| visible.links.get(index)) else {
+                x += 1;
+                continue;
+            };
+            if is_covered(x, y) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            let style = buf[(x, y)].style();
+            let mut text = String::new();
+            while x < area.right()
+                && link_index(markers[(x, y)].style()) == index
+                && buf[(x, y)].style() == style
+                && !is_covered(x, y)
+            {
+                text.push_str(buf[(x, y)].symbol());
+                buf[(x, y)].set_skip(true);
+                x += 1;
+            }
+            links.push(Hyperlink {
+                x: start,
+                y,
+                text,
+                style,
+                url: link.url.clone(),
+            });
+        }
+    }
+    links
+}
+
+pub fn write(out: &mut impl Write, links: &[Hyperlink]) -> io::Result<()> {
+    for link in links {
+        queue!(out, MoveTo(link.x, link.y))?;
+        if let Some(fg) = link.style.fg {
+            queue!(out, SetForegroundColor(fg.into()))?;
+        }
+        if let Some(bg) = link.style.bg {
+            queue!(out, SetBackgroundColor(bg.into()))?;
+        }
+        queue!(out, SetAttribute(Attribute::Underlined))?;
+        for (modifier, attribute) in [
+            (Modifier::BOLD, Attribute::Bold),
+            (Modifier::ITALIC, Attribute::Italic),
+            (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+            (Modifier::REVERSED, Attribute::Reverse),
+        ] {
+            if link.style.add_modifier.contains(modifier) {
+                queue!(out, SetAttribute(attribute))?;
+            }
+        }
+        queue!(
+            out,
+            // control chars in the url would end the sequence early
+            Print(format!(
+                "\x1b]8;;{}\x07{}\x1b]8;;\x07",
+                link.url.replace(char::is_control, ""),
+                link.text
+            )),
+            SetAttribute(Attribute::Reset),
+            ResetColor
+        )?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UI::markdown;
+
+    // renders the message like the chat into an area of the width
+    fn render(message: &str, width: u16) -> Vec<Hyperlink> {
+        let text = markdown::message_text(Vec::new(), message, "");
+        let visible = Visible {
+            lines: text.text.lines,
+            skipped_rows: 0,
+            links: text.links,
+        };
+        let area = Rect::new(0, 0, width, 6);
+        let mut buf = Buffer::empty(area);
+        Paragraph::new(visible.lines.clone())
+            .wrap(Wrap { trim: true })
+            .render(area, &mut buf);
+        take(&mut buf, area, visible, None)
+    }
+
+    #[test]
+    fn wrapped_links_keep_their_url() {
+        let links = render("see https://example.com/a/very/long/path ok", 16);
+        assert!(links.len() > 1);
+        assert!(
+            links
+                .iter()
+                .all(|link| link.url == "https://example.com/a/very/long/path")
+        );
+        let text: String = links.iter().map(|link| link.text.as_str()).collect();
+        assert_eq!(text, "https://example.com/a/very/long/path");
+    }
+
+    #[test]
+    fn parts_of_links_are_not_mistaken_for_other_links() {
+        // the wrapped end of the second url is also part of the first one
+        let links = render("https://b.io/gpath https://a.io/verylongpath", 20);
+        let parts: Vec<(&str, &str)> = links
+            .iter()
+            .map(|link| (link.text.as_str(), link.url.as_str()))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("https://b.io/gpath", "https://b.io/gpath"),
+                ("https://a.io/verylon", "https://a.io/verylongpath"),
+                ("gpath", "https://a.io/verylongpath"),
+            ]
+        );
+    }
+
+    #[test]
+    fn covered_cells_are_left_alone() {
+        let text = markdown::message_text(Vec::new(), "https://example.com", "");
+        let visible = Visible {
+            lines: text.text.lines,
+            skipped_rows: 0,
+            links: text.links,
+        };
+        let area = Rect::new(0, 0, 40, 2);
+        let mut buf = Buffer::empty(area);
+        Paragraph::new(visible.lines.clone()).render(area, &mut buf);
+
+        let links = take(&mut buf, area, visible, Some(Rect::new(8, 0, 4, 1)));
+        let texts: Vec<&str> = links.iter().map(|link| link.text.as_str()).collect();
+        assert_eq!(texts, vec!["https://", "ple.com"]);
+        assert!(!buf[(9, 0)].skip);
+        assert!(buf[(0, 0)].skip);
+    }
+}
@@ -0,0 +1,527 @@
+use crate::UI::tabs::tabs::SelectedTab;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rust_chat_client::config::settings::Settings;
+use rust_chat_client::i18n::catalog::tr;
+use rust_chat_client::types::ConfigError;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+// Action is everything a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum Action {
+    Quit,
+    NextTab,
+    PreviousTab,
+    Send,
+    Newline,
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    ScrollTop,
+    ScrollBottom,
+    HistoryPrevious,
+    HistoryNext,
+    EditLast,
+    SelectPrevious,
+    SelectNext,
+    React,
+    Reply,
+    RowUp,
+    RowDown,
+    OpenMention,
+    RefreshUsers,
+    GroupUsers,
+}
+
+impl Action {
+    // short description which is shown in the footers
+    pub fn description(self) -> String {
+        tr(&format!("action-{}", self.to_string().replace('_', "-")))
+    }
+}
+
+// KeyScope is a set of bindings, global bindings are active in every tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum KeyScope {
+    Global,
+    Chat,
+    Mentions,
+    Users,
+    Help,
+}
+
+impl From<SelectedTab> for KeyScope {
+    fn from(tab: SelectedTab) -> Self {
+        match tab {
+            SelectedTab::Chat => KeyScope::Chat,
+            SelectedTab::Mentions => KeyScope::Mentions,
+            SelectedTab::Users => KeyScope::Users,
+            SelectedTab::Help => KeyScope::Help,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // shift is already part of upper case chars and BackTab
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        KeyBinding { code, modifiers }
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(key_event: &KeyEvent) -> Self {
+        KeyBinding::new(key_event.code, key_event.modifiers)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = ConfigError;
+
+    // parses keys like "esc", "ctrl+c" or "shift+left"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = s.split('+').collect();
+        // "+" itself and "ctrl++" are valid keys
+        if s.ends_with("++") || s == "+" {
+            parts.pop();
+            parts.pop();
+            parts.push("+");
+        }
+        let key = parts.pop().unwrap_or_default();
+
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => return Err(ConfigError::UnknownKey(s.to_string())),
+            }
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdown" => KeyCode::PageDown,
+            "space" => KeyCode::Char(' '),
+            k if k.len() > 1 && k.starts_with('f') => match k[1..].parse::<u8>() {
+                Ok(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                _ => return Err(ConfigError::UnknownKey(s.to_string())),
+            },
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    // terminals send shifted chars in upper case
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(ConfigError::UnknownKey(s.to_string())),
+                }
+            }
+        };
+
+        Ok(KeyBinding::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl ")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt ")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift ")?;
+        }
+        match self.code {
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+// Keymap maps keys to actions, separated by scope
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyScope, Vec<(KeyBinding, Action)>>,
+}
+
+fn default_bindings(scope: KeyScope) -> Vec<(Action, &'static [&'static str])> {
+    match scope {
+        KeyScope::Global => vec![
+            (Action::Quit, &["esc", "ctrl+c"]),
+            (Action::NextTab, &["tab"]),
+            (Action::PreviousTab, &["backtab"]),
+        ],
+        KeyScope::Chat => vec![
+            (Action::Send, &["enter"]),
+            // shift+enter is only reported by terminals with the kitty keyboard protocol
+            (Action::Newline, &["shift+enter", "alt+enter"]),
+            (Action::ScrollUp, &["up"]),
+            (Action::ScrollDown, &["down"]),
+            (Action::PageUp, &["pageup"]),
+            (Action::PageDown, &["pagedown"]),
+            (Action::ScrollTop, &["ctrl+home"]),
+            (Action::ScrollBottom, &["ctrl+end"]),
+            (Action::HistoryPrevious, &["shift+left"]),
+            (Action::HistoryNext, &["shift+right"]),
+            (Action::EditLast, &["ctrl+up"]),
+            (Action::SelectPrevious, &["alt+up"]),
+            (Action::SelectNext, &["alt+down"]),
+            (Action::React, &["ctrl+r"]),
+            (Action::Reply, &["alt+r"]),
+        ],
+        KeyScope::Mentions => vec![
+            (Action::RowUp, &["up"]),
+            (Action::RowDown, &["down"]),
+            (Action::OpenMention, &["enter"]),
+        ],
+        KeyScope::Users => vec![
+            (Action::RowUp, &["up"]),
+            (Action::RowDown, &["down"]),
+            (Action::RefreshUsers, &["u"]),
+            (Action::GroupUsers, &["g"]),
+        ],
+        KeyScope::Help => vec![(Action::RowUp, &["up"]), (Action::RowDown, &["down"])],
+    }
+}
+
+impl Keymap {
+    // builds the keymap out of the defaults and the [keymap] section of the settings.
+    // An action configured by the user replaces all of its default keys in that scope.
+    pub fn from_settings(settings: &Settings) -> Result<Keymap, ConfigError> {
+        let mut actions: HashMap<KeyScope, Vec<(Action, Vec<String>)>> = HashMap::new();
+        for scope in KeyScope::iter() {
+            let defaults = default_bindings(scope)
+                .into_iter()
+                .map(|(action, keys)| (action, keys.iter().map(|k| k.to_string()).collect()))
+                .collect();
+            actions.insert(scope, defaults);
+        }
+
+        for (scope_name, overrides) in &settings.keymap {
+            let scope = KeyScope::from_str(scope_name)
+                .map_err(|_| ConfigError::UnknownScope(scope_name.clone()))?;
+            let scope_actions = actions.entry(scope).or_default();
+            for (action_name, keys) in overrides {
+                let action = Action::from_str(action_name)
+                    .map_err(|_| ConfigError::UnknownAction(action_name.clone()))?;
+                scope_actions.retain(|(a, _)| *a != action);
+                scope_actions.push((action, keys.keys()));
+            }
+        }
+
+        let mut bindings = HashMap::new();
+        for (scope, scope_actions) in actions {
+            let mut scope_bindings = Vec::new();
+            for (action, keys) in scope_actions {
+                for key in keys {
+                    scope_bindings.push((KeyBinding::from_str(&key)?, action));
+                }
+            }
+            bindings.insert(scope, scope_bindings);
+        }
+
+        let keymap = Keymap { bindings };
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    // a key must not trigger two actions in the same scope
+    // and tab bindings must not shadow global ones
+    fn check_conflicts(&self) -> Result<(), ConfigError> {
+        let mut conflicts = Vec::new();
+        let global = self.scope_bindings(KeyScope::Global);
+
+        for scope in KeyScope::iter() {
+            let scope_bindings = self.scope_bindings(scope);
+            for (i, (key, action)) in scope_bindings.iter().enumerate() {
+                for (other_key, other_action) in &scope_bindings[i + 1..] {
+                    if key == other_key && action != other_action {
+                        conflicts.push(format!(
+                            "[{key}] is bound to '{action}' and '{other_action}' in '{scope}'"
+                        ));
+                    }
+                }
+                if scope == KeyScope::Global {
+                    continue;
+                }
+                if let Some((_, global_action)) = global.iter().find(|(k, _)| k == key) {
+                    conflicts.push(format!(
+                        "[{key}] is bound to '{action}' in '{scope}' and to '{global_action}' globally"
+                    ));
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::KeyConflict(conflicts.join("\n")))
+        }
+    }
+
+    fn scope_bindings(&self, scope: KeyScope) -> &[(KeyBinding, Action)] {
+        self.bindings
+            .get(&scope)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // looks up the action of a key event, bindings of the tab win over global ones
+    pub fn action_for(&self, scope: KeyScope, key_event: &KeyEvent) -> Option<Action> {
+        let key = KeyBinding::from(key_event);
+        [scope, KeyScope::Global]
+            .into_iter()
+            .flat_map(|s| self.scope_bindings(s))
+            .find(|(k, _)| *k == key)
+            .map(|(_, action)| *action)
+    }
+
+    // all keys bound to an action, joined for display e.g. "Esc | Ctrl c"
+    pub fn keys_for(&self, scope: KeyScope, action: Action) -> String {
+        self.scope_bindings(scope)
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(k, _)| k.to_string())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    // help text for the given actions e.g. "[↑] move up | [u] refresh users"
+    pub fn hint(&self, scope: KeyScope, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .filter_map(|action| {
+                let keys = self.keys_for(scope, *action);
+                if keys.is_empty() {
+                    None
+                } else {
+                    Some(format!("[{}] {}", keys, action.description()))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    // footer of a tab, generated from all bindings of its scope
+    pub fn footer(&self, scope: KeyScope) -> String {
+        let mut actions: Vec<Action> = Vec::new();
+        for (_, action) in self.scope_bindings(scope) {
+            if !actions.contains(action) {
+                actions.push(*action);
+            }
+        }
+        self.hint(scope, &actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifted_chars_are_upper_case() {
+        let shifted = KeyBinding::from_str("shift+a").unwrap();
+        assert_eq!(shifted, KeyBinding::from_str("A").unwrap());
+        assert_eq!(
+            shifted,
+            KeyBinding::new(KeyCode::Char('A'), KeyModifiers::SHIFT)
+        );
+        assert_ne!(shifted, KeyBinding::from_str("a").unwrap());
+    }
+
+    fn key(s: &str) -> KeyBinding {
+        KeyBinding::from_str(s).unwrap()
+    }
+
+    fn keymap(config: &str) -> Result<Keymap, ConfigError> {
+        Keymap::from_settings(&toml::from_str(config).unwrap())
+    }
+
+    fn event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn keys_with_modifiers() {
+        assert_eq!(
+            key("ctrl+c"),
+            KeyBinding::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            key("Ctrl+Alt+Left"),
+            KeyBinding::new(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(key("esc"), key("escape"));
+        assert_eq!(
+            key("f5"),
+            KeyBinding::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            key("space"),
+            KeyBinding::new(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        // shift is part of backtab
+        assert_eq!(key("shift+backtab"), key("backtab"));
+    }
+
+    #[test]
+    fn plus_is_a_key() {
+        assert_eq!(
+            key("+"),
+            KeyBinding::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            key("ctrl++"),
+            KeyBinding::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        for s in ["hyper+x", "f13", "foo", ""] {
+            assert!(
+                matches!(KeyBinding::from_str(s), Err(ConfigError::UnknownKey(_))),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn keys_are_displayed_for_the_footers() {
+        assert_eq!(key("ctrl+c").to_string(), "Ctrl c");
+        assert_eq!(key("alt+shift+up").to_string(), "Alt Shift ↑");
+        assert_eq!(key("backtab").to_string(), "Shift Tab");
+        assert_eq!(key("space").to_string(), "Space");
+        assert_eq!(key("pagedown").to_string(), "PgDn");
+        assert_eq!(key("f12").to_string(), "F12");
+    }
+
+    #[test]
+    fn defaults_without_config() {
+        let keymap = keymap("").unwrap();
+        let enter = event(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.action_for(KeyScope::Chat, &enter),
+            Some(Action::Send)
+        );
+        // global bindings work in every tab
+        let esc = event(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(KeyScope::Users, &esc), Some(Action::Quit));
+        assert_eq!(
+            keymap.keys_for(KeyScope::Global, Action::Quit),
+            "Esc | Ctrl c"
+        );
+        assert_eq!(keymap.action_for(KeyScope::Help, &enter), None);
+    }
+
+    #[test]
+    fn configured_keys_replace_the_defaults() {
+        let keymap = keymap(
+            r#"
+            [keymap.global]
+            quit = "ctrl+q"
+            [keymap.chat]
+            scroll_up = ["k", "up"]
+            "#,
+        )
+        .unwrap();
+        let quit = event(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        let esc = event(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(KeyScope::Chat, &quit), Some(Action::Quit));
+        assert_eq!(keymap.action_for(KeyScope::Chat, &esc), None);
+        let k = event(KeyCode::Char('k'), KeyModifiers::NONE);
+        let up = event(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.action_for(KeyScope::Chat, &k),
+            Some(Action::ScrollUp)
+        );
+        assert_eq!(
+            keymap.action_for(KeyScope::Chat, &up),
+            Some(Action::ScrollUp)
+        );
+        // the other defaults of the scope are kept
+        let tab = event(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.action_for(KeyScope::Chat, &tab),
+            Some(Action::NextTab)
+        );
+    }
+
+    #[test]
+    fn unknown_scopes_and_actions_are_rejected() {
+        assert!(matches!(
+            keymap("[keymap.settings]\nquit = \"q\""),
+            Err(ConfigError::UnknownScope(_))
+        ));
+        assert!(matches!(
+            keymap("[keymap.chat]\nfly = \"q\""),
+            Err(ConfigError::UnknownAction(_))
+        ));
+    }
+
+    #[test]
+    fn conflicts_in_a_scope_are_reported() {
+        let err = keymap("[keymap.users]\nrefresh_users = \"g\"").unwrap_err();
+        assert!(matches!(err, ConfigError::KeyConflict(ref c) if c.contains("'users'")));
+    }
+
+    #[test]
+    fn tab_keys_must_not_shadow_global_ones() {
+        let err = keymap("[keymap.chat]\nsend = \"tab\"").unwrap_err();
+        assert!(
+            matches!(err, ConfigError::KeyConflict(ref c) if c.contains("globally")),
+            "{err:?}"
+        );
+        // the same key for the same action is no conflict
+        assert!(keymap("[keymap.help]\nrow_up = [\"up\", \"up\"]").is_ok());
+    }
+}
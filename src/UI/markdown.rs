@@ -0,0 +1,203 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use rust_chat_client::chat::markdown::{self, Block, Format, Inline};
+use rust_chat_client::chat::mentions;
+
+use crate::UI::app::INDENT;
+use crate::UI::message_buffer::{ChatText, LinkSpan};
+use crate::UI::theme;
+use crate::UI::user_interface::mention_span;
+
+// the keywords and the comment start of the languages which are highlighted
+fn language(name: &str) -> Option<(&'static str, &'static str)> {
+    match name {
+        "rust" | "rs" => Some((
+            "as async await const else enum false fn for if impl in let loop match mod move \
+             mut pub ref return self Self static struct trait true type use where while",
+            "//",
+        )),
+        "go" => Some((
+            "case chan const default defer else false for func go if import interface map \
+             nil package range return select struct switch true type var",
+            "//",
+        )),
+        "python" | "py" => Some((
+            "and as async await class def elif else except False finally for from if \
+             import in is lambda None not or pass raise return True try while with yield",
+            "#",
+        )),
+        "javascript" | "js" | "typescript" | "ts" => Some((
+            "async await class const else export false for function if import interface \
+             let new null return this true type typeof undefined var while",
+            "//",
+        )),
+        "sh" | "bash" | "shell" => Some((
+            "case do done echo elif else esac export fi for function if in local return \
+             then while",
+            "#",
+        )),
+        _ => None,
+    }
+}
+
+// the text of a chat message, the first line starts with the prefix and the
+// following ones are indented. The spans of the links are recorded with their urls.
+pub fn message_text(prefix: Vec<Span<'static>>, text: &str, own_name: &str) -> ChatText {
+    let mut lines = Vec::new();
+    let mut links = Vec::new();
+    let mut prefix = Some(prefix);
+    for block in markdown::parse(text) {
+        match block {
+            Block::Text(inlines) => {
+                let mut spans = prefix.take().unwrap_or_else(|| vec![Span::from(INDENT)]);
+                for inline in &inlines {
+                    if inline.format.link {
+                        links.push(LinkSpan {
+                            line: lines.len(),
+                            span: spans.len(),
+                            url: inline.text.clone(),
+                        });
+                    }
+                    spans.extend(inline_spans(inline, own_name));
+                }
+                lines.push(Line::from(spans));
+            }
+            Block::Code {
+                language,
+                lines: code,
+            } => {
+                if let Some(prefix) = prefix.take() {
+                    lines.push(Line::from(prefix));
+                }
+                lines.extend(code.iter().map(|line| code_line(&language, line)));
+            }
+        }
+    }
+    if let Some(prefix) = prefix {
+        lines.push(Line::from(prefix));
+    }
+    ChatText {
+        text: Text::from(lines),
+        links,
+    }
+}
+
+fn style(format: Format) -> Style {
+    let theme = theme::current();
+    let mut style = Style::new();
+    if format.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if format.italic {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if format.strike {
+        style = style.add_modifier(Modifier::CROSSED_OUT);
+    }
+    if format.code {
+        style = style.fg(theme.code).bg(theme.code_bg);
+    }
+    if format.link {
+        style = style.fg(theme.link).add_modifier(Modifier::UNDERLINED);
+    }
+    style
+}
+
+// mentions of the own name are highlighted, except in code and links
+fn inline_spans(inline: &Inline, own_name: &str) -> Vec<Span<'static>> {
+    let style = style(inline.format);
+    if inline.format.code || inline.format.link {
+        return vec![Span::styled(inline.text.clone(), style)];
+    }
+    let text = &inline.text;
+    let mut spans = Vec::new();
+    let mut end = 0;
+    for mention in mentions::mentions_of(text, own_name) {
+        if mention.range.start > end {
+            spans.push(Span::styled(
+                text[end..mention.range.start].to_string(),
+                style,
+            ));
+        }
+        spans.push(mention_span(text[mention.range.clone()].to_string()).patch_style(style));
+        end = mention.range.end;
+    }
+    if end < text.len() {
+        spans.push(Span::styled(text[end..].to_string(), style));
+    }
+    spans
+}
+
+fn code_line(language: &str, line: &str) -> Line<'static> {
+    let style = style(Format {
+        code: true,
+        ..Format::default()
+    });
+    let line = line.replace('\t', "    ");
+    let code = line.trim_start_matches(' ');
+    // the wrapping of the chat trims normal spaces, empty lines keep their background
+    let indent = "\u{a0}".repeat((line.len() - code.len()).max(usize::from(code.is_empty())));
+    let mut spans = vec![Span::from(INDENT), Span::styled(indent, style)];
+    match self::language(language) {
+        Some((keywords, comment)) => spans.extend(highlight(code, keywords, comment, style)),
+        None => spans.push(Span::styled(code.to_string(), style)),
+    }
+    Line::from(spans)
+}
+
+// a simple highlighting of keywords, strings, numbers and comments
+fn highlight(code: &str, keywords: &str, comment: &str, style: Style) -> Vec<Span<'static>> {
+    let theme = theme::current();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+    while let Some(c) = code[i..].chars().next() {
+        let rest = &code[i..];
+        let (len, token_style) = if rest.starts_with(comment) {
+            (rest.len(), style.fg(theme.dim))
+        } else if c == '"' || (c == '\'' && comment == "#") {
+            // strings end at the next unescaped quote or with the line
+            let mut escaped = false;
+            let end = rest[1..]
+                .find(|ch| {
+                    let end = ch == c && !escaped;
+                    escaped = ch == '\\' && !escaped;
+                    end
+                })
+                .map_or(rest.len(), |end| end + 2);
+            (end, style.fg(theme.success))
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|ch: char| !ch.is_alphanumeric() && ch != '.' && ch != '_')
+                .unwrap_or(rest.len());
+            (len, style.fg(theme.name))
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|ch: char| !ch.is_alphanumeric() && ch != '_')
+                .unwrap_or(rest.len());
+            if keywords
+                .split_whitespace()
+                .any(|keyword| keyword == &rest[..len])
+            {
+                (len, style.fg(theme.info).add_modifier(Modifier::BOLD))
+            } else {
+                plain.push_str(&rest[..len]);
+                i += len;
+                continue;
+            }
+        } else {
+            plain.push(c);
+            i += c.len_utf8();
+            continue;
+        };
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut plain), style));
+        }
+        spans.push(Span::styled(rest[..len].to_string(), token_style));
+        i += len;
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, style));
+    }
+    spans
+}
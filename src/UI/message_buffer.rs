@@ -0,0 +1,408 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Paragraph, Wrap};
+use std::collections::VecDeque;
+use std::fmt;
+
+pub const DEFAULT_MAX_MESSAGES: usize = 5000;
+
+// MessageBuffer is a ring buffer of the chat lines. When it is full the oldest
+// line is dropped. The wrapped height of every line is cached for the current
+// width, so scrolling and rendering don't have to wrap the whole history.
+// A line of the buffer is a text, so a message with line breaks is one line.
+// A line can have footer lines below it, e.g. the reactions to a message.
+// Lines and footers are built by a Render, which builds them again when the theme changes.
+#[derive(Debug)]
+pub struct MessageBuffer {
+    lines: VecDeque<ChatText>,
+    footers: VecDeque<Vec<Line<'static>>>,
+    renders: VecDeque<Render>,
+    footer_renders: VecDeque<Render<Vec<Line<'static>>>>,
+    heights: VecDeque<usize>,
+    total_height: usize,
+    width: u16,
+    capacity: usize,
+    // ids of the lines in ascending order, a line keeps its id while it is in the buffer
+    ids: VecDeque<usize>,
+    next_id: usize,
+}
+
+impl MessageBuffer {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        MessageBuffer {
+            lines: VecDeque::with_capacity(capacity),
+            footers: VecDeque::with_capacity(capacity),
+            renders: VecDeque::with_capacity(capacity),
+            footer_renders: VecDeque::with_capacity(capacity),
+            heights: VecDeque::with_capacity(capacity),
+            total_height: 0,
+            width: 0,
+            capacity,
+            ids: VecDeque::with_capacity(capacity),
+            next_id: 0,
+        }
+    }
+
+    // Push appends a line and returns the number of rows which got dropped at the top
+    pub fn push(&mut self, render: Render) -> usize {
+        let line = render.build();
+        let mut dropped = 0;
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+            self.footers.pop_front();
+            self.renders.pop_front();
+            self.footer_renders.pop_front();
+            dropped = self.heights.pop_front().unwrap_or(0);
+            self.total_height -= dropped;
+            self.ids.pop_front();
+        }
+        let height = line_height(&line.text, self.width);
+        self.total_height += height;
+        self.heights.push_back(height);
+        self.lines.push_back(line);
+        self.footers.push_back(Vec::new());
+        self.renders.push_back(render);
+        self.footer_renders.push_back(Render::new(Vec::new));
+        self.ids.push_back(self.next_id);
+        self.next_id += 1;
+        dropped
+    }
+
+    // id of the last pushed line
+    pub fn last_id(&self) -> usize {
+        self.next_id.saturating_sub(1)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.index_of(id).is_some()
+    }
+
+    fn index_of(&self, id: usize) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    fn update_height(&mut self, index: usize) {
+        let height = entry_height(&self.lines[index].text, &self.footers[index], self.width);
+        self.total_height = self.total_height - self.heights[index] + height;
+        self.heights[index] = height;
+    }
+
+    // Replace changes the line with the given id, returns false if it is not in the buffer.
+    // The footer of the line is kept.
+    pub fn replace(&mut self, id: usize, render: Render) -> bool {
+        let Some(index) = self.index_of(id) else {
+            return false;
+        };
+        self.lines[index] = render.build();
+        self.renders[index] = render;
+        self.update_height(index);
+        true
+    }
+
+    // Set_footer changes the lines below the line with the given id
+    pub fn set_footer(&mut self, id: usize, render: Render<Vec<Line<'static>>>) -> bool {
+        let Some(index) = self.index_of(id) else {
+            return false;
+        };
+        self.footers[index] = render.build();
+        self.footer_renders[index] = render;
+        self.update_height(index);
+        true
+    }
+
+    // Remove deletes the line with the given id, returns false if it is not in the buffer
+    pub fn remove(&mut self, id: usize) -> bool {
+        let Some(index) = self.index_of(id) else {
+            return false;
+        };
+        self.lines.remove(index);
+        self.footers.remove(index);
+        self.renders.remove(index);
+        self.footer_renders.remove(index);
+        self.ids.remove(index);
+        self.total_height -= self.heights.remove(index).unwrap_or(0);
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    // the first line of the text, e.g. the one with the name of the sender
+    pub fn get(&self, index: usize) -> Option<&Line<'static>> {
+        self.lines.get(index)?.text.lines.first()
+    }
+
+    // builds all lines again, e.g. with the colors of a new theme
+    pub fn rebuild(&mut self) {
+        self.lines = self.renders.iter().map(Render::build).collect();
+        self.footers = self.footer_renders.iter().map(Render::build).collect();
+        self.update_heights();
+    }
+
+    // recalculates the heights if the chat has been resized
+    pub fn set_width(&mut self, width: u16) {
+        if width == self.width {
+            return;
+        }
+        self.width = width;
+        self.update_heights();
+    }
+
+    fn update_heights(&mut self) {
+        self.heights = self
+            .lines
+            .iter()
+            .zip(self.footers.iter())
+            .map(|(line, footer)| entry_height(&line.text, footer, self.width))
+            .collect();
+        self.total_height = self.heights.iter().sum();
+    }
+
+    // number of rows of all lines wrapped to the current width
+    pub fn total_height(&self) -> usize {
+        self.total_height
+    }
+
+    // returns the index of the line which is rendered at the given row
+    // and the row within that line
+    pub fn line_at_row(&self, row: usize) -> Option<(usize, usize)> {
+        let mut top = 0;
+        for (i, height) in self.heights.iter().enumerate() {
+            if row < top + height {
+                return Some((i, row - top));
+            }
+            top += height;
+        }
+        None
+    }
+
+    // first row and number of rows of the line with the given id, including its footer
+    pub fn rows_of(&self, id: usize) -> Option<(usize, usize)> {
+        let index = self.index_of(id)?;
+        let top = self.heights.iter().take(index).sum();
+        Some((top, self.heights[index]))
+    }
+
+    // Visible returns the lines which cover the rows from `offset` to `offset + height`
+    // and the number of rows of the first line which are above `offset`.
+    // The line with the id `highlighted` is shown reversed.
+    pub fn visible(&self, offset: usize, height: usize, highlighted: Option<usize>) -> Visible {
+        let Some((first, skipped_rows)) = self.line_at_row(offset) else {
+            return Visible::default();
+        };
+        let highlighted = highlighted.and_then(|id| self.index_of(id));
+
+        let mut visible = Visible {
+            skipped_rows,
+            ..Visible::default()
+        };
+        let mut rows = 0;
+        for (i, line_height) in self.heights.iter().enumerate().skip(first) {
+            if rows >= height + skipped_rows {
+                break;
+            }
+            let ChatText { text, links } = self.lines[i].clone();
+            let top = visible.lines.len();
+            visible.links.extend(links.into_iter().map(|link| LinkSpan {
+                line: top + link.line,
+                ..link
+            }));
+            if highlighted == Some(i) {
+                let style = Style::new().add_modifier(Modifier::REVERSED);
+                visible
+                    .lines
+                    .extend(text.lines.into_iter().map(|l| l.patch_style(style)));
+            } else {
+                visible.lines.extend(text.lines);
+            }
+            visible.lines.extend(self.footers[i].iter().cloned());
+            rows += line_height;
+        }
+        visible
+    }
+}
+
+// a link in a text of the chat, the span of the line shows the url
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkSpan {
+    pub line: usize,
+    pub span: usize,
+    pub url: String,
+}
+
+// ChatText is a line of the chat with its links, which are recorded when the
+// markdown of a message is rendered
+#[derive(Debug, Clone, Default)]
+pub struct ChatText {
+    pub text: Text<'static>,
+    pub links: Vec<LinkSpan>,
+}
+
+impl From<Text<'static>> for ChatText {
+    fn from(text: Text<'static>) -> Self {
+        ChatText {
+            text,
+            links: Vec::new(),
+        }
+    }
+}
+
+// the lines in the view of the chat, the lines of the links are indexes of `lines`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Visible {
+    pub lines: Vec<Line<'static>>,
+    // rows of the first line which are above the view
+    pub skipped_rows: usize,
+    pub links: Vec<LinkSpan>,
+}
+
+// Render builds a line of the chat from its message. The spans take their colors
+// from the current theme, so the line is built again when the theme changes.
+pub struct Render<T = ChatText>(Box<dyn Fn() -> T>);
+
+impl<T> Render<T> {
+    pub fn new(build: impl Fn() -> T + 'static) -> Render<T> {
+        Render(Box::new(build))
+    }
+
+    pub fn build(&self) -> T {
+        (self.0)()
+    }
+}
+
+impl Render {
+    // for the builders of lines, texts and spans
+    pub fn text<L: Into<Text<'static>>>(build: impl Fn() -> L + 'static) -> Render {
+        Render::new(move || ChatText::from(build().into()))
+    }
+}
+
+impl<T> fmt::Debug for Render<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Render")
+    }
+}
+
+// number of rows a line takes up when it is wrapped to the given width
+pub fn line_height<'a>(line: &(impl Into<Text<'a>> + Clone), width: u16) -> usize {
+    Paragraph::new(line.clone())
+        .wrap(Wrap { trim: true })
+        .line_count(width)
+}
+
+// number of rows of a line and its footer
+fn entry_height(line: &Text, footer: &[Line], width: u16) -> usize {
+    line_height(line, width) + footer.iter().map(|l| line_height(l, width)).sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &'static str) -> Render {
+        Render::text(move || s)
+    }
+
+    // a buffer 10 columns wide with the given lines
+    fn buffer(capacity: usize, lines: &[&'static str]) -> MessageBuffer {
+        let mut buffer = MessageBuffer::new(capacity);
+        buffer.set_width(10);
+        for line in lines {
+            buffer.push(text(line));
+        }
+        buffer
+    }
+
+    fn contents(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn full_buffers_drop_the_oldest_line() {
+        let mut buffer = buffer(3, &["one", "two two two two", "three"]);
+        assert_eq!(buffer.total_height(), 4);
+
+        assert_eq!(buffer.push(text("four")), 1);
+        assert_eq!(buffer.push(text("five")), 2);
+        assert_eq!(buffer.total_height(), 3);
+        assert_eq!(buffer.get(0).unwrap().to_string(), "three");
+        // the ids go on, the dropped ones are gone
+        assert_eq!(buffer.last_id(), 4);
+        assert!(!buffer.contains(1));
+        assert!(buffer.contains(2));
+    }
+
+    #[test]
+    fn zero_capacity_keeps_one_line() {
+        let mut buffer = buffer(0, &["one", "two"]);
+        assert_eq!(buffer.capacity(), 1);
+        assert_eq!(buffer.total_height(), 1);
+        assert_eq!(buffer.push(text("three")), 1);
+    }
+
+    #[test]
+    fn heights_follow_the_width_and_changes() {
+        let mut buffer = buffer(10, &["one", "two two two two"]);
+        assert_eq!(buffer.rows_of(1), Some((1, 2)));
+
+        buffer.set_width(40);
+        assert_eq!(buffer.total_height(), 2);
+
+        buffer.set_width(10);
+        assert!(buffer.replace(0, text("one one one one")));
+        assert!(buffer.set_footer(1, Render::new(|| vec![Line::raw("👍 1")])));
+        assert_eq!(buffer.rows_of(0), Some((0, 2)));
+        assert_eq!(buffer.rows_of(1), Some((2, 3)));
+        assert_eq!(buffer.total_height(), 5);
+
+        assert!(buffer.remove(0));
+        assert_eq!(buffer.rows_of(1), Some((0, 3)));
+        assert_eq!(buffer.total_height(), 3);
+    }
+
+    #[test]
+    fn lines_are_found_by_id() {
+        let mut buffer = buffer(3, &["a", "b", "c", "d"]);
+        assert!(buffer.remove(2));
+        assert!(!buffer.contains(0));
+        assert!(!buffer.contains(2));
+        assert!(buffer.contains(3));
+        assert!(!buffer.replace(2, text("x")));
+        assert!(!buffer.remove(7));
+        assert!(buffer.replace(3, text("x")));
+        assert_eq!(buffer.get(1).unwrap().to_string(), "x");
+    }
+
+    #[test]
+    fn visible_returns_the_lines_of_the_rows() {
+        let buffer = buffer(10, &["one", "two two two two", "three", "four"]);
+        assert_eq!(buffer.line_at_row(2), Some((1, 1)));
+
+        // the second row of "two two two two" is the first one in the view
+        let visible = buffer.visible(2, 2, None);
+        assert_eq!(visible.skipped_rows, 1);
+        assert_eq!(contents(&visible.lines), vec!["two two two two", "three"]);
+
+        let visible = buffer.visible(0, 1, None);
+        assert_eq!(
+            (contents(&visible.lines), visible.skipped_rows),
+            (vec!["one".to_string()], 0)
+        );
+
+        assert_eq!(buffer.visible(5, 3, None), Visible::default());
+    }
+
+    #[test]
+    fn the_highlighted_line_is_reversed() {
+        let buffer = buffer(10, &["one", "two"]);
+        let lines = buffer.visible(0, 2, Some(1)).lines;
+        assert!(!lines[0].style.add_modifier.contains(Modifier::REVERSED));
+        assert!(lines[1].style.add_modifier.contains(Modifier::REVERSED));
+    }
+}
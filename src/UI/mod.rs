@@ -1,5 +1,14 @@
 pub mod app;
 pub mod event;
+pub mod hyperlinks;
 pub mod input_history;
+pub mod keymap;
+pub mod markdown;
+pub mod message_buffer;
+pub mod notifier;
+pub mod reactions;
 pub mod tabs;
+pub mod terminal;
+pub mod theme;
+pub mod typing;
 pub mod user_interface;
@@ -0,0 +1,64 @@
+use std::io::{Write, stdout};
+
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::SetTitle;
+use rust_chat_client::chat::notifications::{self, Notification};
+use rust_chat_client::config::settings::NotificationSettings;
+use rust_chat_client::i18n::catalog::{tr, tr_count};
+
+// Notifier tells the user about events while the terminal doesn't have the focus
+#[derive(Debug)]
+pub struct Notifier {
+    settings: NotificationSettings,
+    // focus changes are only reported by terminals which support it
+    focused: bool,
+    // notifications since the terminal lost the focus, shown in the window title
+    unread: usize,
+}
+
+impl Notifier {
+    pub fn new(settings: NotificationSettings) -> Self {
+        Notifier {
+            settings,
+            focused: true,
+            unread: 0,
+        }
+    }
+
+    pub fn notify(&mut self, notification: Notification) {
+        if !self.settings.events.contains(&notification.kind)
+            || (self.focused && !self.settings.when_focused)
+        {
+            return;
+        }
+        let mut out = stdout();
+        let sequences = notifications::escape_sequences(
+            self.settings.bell,
+            self.settings.desktop,
+            &notification,
+        );
+        if !sequences.is_empty() {
+            let _ = out.write_all(sequences.as_bytes());
+        }
+        if self.settings.title && !self.focused {
+            self.unread += 1;
+            let _ = execute!(out, SetTitle(tr_count("window-title-unread", self.unread)));
+        }
+        let _ = out.flush();
+
+        if let Some(command) = &self.settings.command {
+            // a failing command must not disturb the chat
+            let _ = notifications::run_hook(command, &notification);
+        }
+    }
+
+    pub fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        if focused && self.unread > 0 {
+            self.unread = 0;
+            if self.settings.title {
+                let _ = execute!(stdout(), SetTitle(tr("window-title")));
+            }
+        }
+    }
+}
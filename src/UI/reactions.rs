@@ -0,0 +1,92 @@
+use crate::UI::user_interface::{accent_span, dim_span};
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use rust_chat_client::chat::reactions::ReactionCount;
+
+// emojis of the reaction picker, the digits 1 to 8 choose them directly
+pub const PICKER_EMOJIS: [&str; 8] = ["👍", "❤️", "😂", "😮", "😢", "🎉", "👀", "🔥"];
+
+// ReactionPicker is open while the user chooses a reaction to the selected message
+#[derive(Debug)]
+pub struct ReactionPicker {
+    pub message_id: String,
+    // client id for reactions to private messages, empty for group messages
+    pub receiver: String,
+    pub index: usize,
+}
+
+impl ReactionPicker {
+    pub fn new(message_id: String, receiver: String) -> Self {
+        ReactionPicker {
+            message_id,
+            receiver,
+            index: 0,
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.index = (self.index + 1) % PICKER_EMOJIS.len();
+    }
+
+    pub fn previous(&mut self) {
+        self.index = (self.index + PICKER_EMOJIS.len() - 1) % PICKER_EMOJIS.len();
+    }
+
+    // the digits choose the emoji with that number
+    pub fn select_digit(&mut self, c: char) -> bool {
+        match c.to_digit(10).map(|d| d as usize) {
+            Some(d) if (1..=PICKER_EMOJIS.len()).contains(&d) => {
+                self.index = d - 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        PICKER_EMOJIS[self.index]
+    }
+
+    // the /react command for the chosen emoji
+    pub fn command(&self) -> String {
+        let command = format!("/react {} {}", self.message_id, self.emoji());
+        if self.receiver.is_empty() {
+            command
+        } else {
+            format!("{command} {}", self.receiver)
+        }
+    }
+
+    pub fn line(&self) -> Line<'static> {
+        let spans: Vec<Span> = PICKER_EMOJIS
+            .iter()
+            .enumerate()
+            .map(|(i, emoji)| {
+                let text = format!(" {} {} ", i + 1, emoji);
+                if i == self.index {
+                    accent_span(text).reversed()
+                } else {
+                    Span::from(text)
+                }
+            })
+            .collect();
+        Line::from(spans)
+    }
+}
+
+// the line under a message with its reactions, the own ones are highlighted
+pub fn reaction_footer(counts: &[ReactionCount]) -> Vec<Line<'static>> {
+    if counts.is_empty() {
+        return Vec::new();
+    }
+    let mut spans = vec![dim_span(String::from("╰"))];
+    for count in counts {
+        let text = format!(" {} {}", count.emoji, count.count);
+        spans.push(if count.own {
+            accent_span(text)
+        } else {
+            dim_span(text)
+        });
+    }
+    vec![Line::from(spans)]
+}
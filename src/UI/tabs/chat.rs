@@ -1,6 +1,7 @@
 use crate::UI::app::App;
-use crate::UI::user_interface::blue_span;
-use crate::types;
+use crate::UI::hyperlinks;
+use crate::UI::keymap::{Action, KeyScope};
+use crate::UI::theme;
 use color_eyre::owo_colors::OwoColorize;
 use ratatui::prelude::Rect;
 use ratatui::style::Modifier;
@@ -10,37 +11,129 @@ use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Style, Stylize},
-    widgets::{Block, BorderType, Paragraph, Scrollbar, ScrollbarOrientation, Widget},
+    widgets::{Block, BorderType, Clear, Paragraph, Scrollbar, ScrollbarOrientation, Widget},
 };
+use rust_chat_client::i18n::catalog::{tr, tr_args, tr_count};
+
+// the input grows with its lines up to this height, more lines are scrolled
+const MAX_INPUT_LINES: usize = 10;
+const MIN_INPUT_LINES: usize = 3;
 
 pub fn render_chat_tab(app: &mut App, frame: &mut Frame, area: Rect) {
+    // + 2 for the border, the chat keeps at least half of the tab
+    let input_lines = app
+        .text_input
+        .lines()
+        .len()
+        .clamp(MIN_INPUT_LINES, MAX_INPUT_LINES) as u16;
+    let input_height = (input_lines + 2).min((area.height / 2).max(MIN_INPUT_LINES as u16 + 2));
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(3), Constraint::Length(5)])
+        .constraints([Constraint::Min(3), Constraint::Length(input_height)])
         .split(area);
-    app.chat_size = chunks[0].as_size();
+    app.chat_area = chunks[0];
+
+    // the scroll offset depends on the width, so it is updated on every resize
+    let inner = app.chat_inner_area();
+    app.messages.set_width(inner.width);
+    let max_scroll = app.max_scroll();
+    if app.follow_tail || app.vertical_scroll > max_scroll {
+        app.vertical_scroll = max_scroll;
+    }
 
-    let style = Style::new().fg(types::DARK_TURKIS_COLOR).bg(Color::Black);
+    let theme = theme::current();
+    let style = Style::new().fg(theme.border).bg(theme.background);
 
     // Nachrichtenbereich (oben)
-    let message_block = Block::bordered()
+    let mut message_block = Block::bordered()
         .title(format!(" {} ", app.title.clone()))
         .title_alignment(Alignment::Center)
         .border_type(BorderType::Rounded)
         .border_style(style);
+    if app.unseen_messages > 0 {
+        message_block = message_block.title_bottom(
+            Line::from(format!(
+                " {} ",
+                tr_count("new-messages-below", app.unseen_messages)
+            ))
+            .fg(theme.info)
+            .right_aligned(),
+        );
+    }
 
-    let message_field = Paragraph::new(app.messages.lines.clone())
+    let typing = app.typing.names();
+    if !typing.is_empty() {
+        let text = match typing.as_slice() {
+            [name] => tr_args("typing-one", &[("name", name.clone())]),
+            names => tr_args("typing-many", &[("names", names.join(", "))]),
+        };
+        message_block = message_block.title_bottom(
+            Line::from(format!(" {text} "))
+                .fg(theme.info)
+                .left_aligned(),
+        );
+    }
+
+    // only the visible lines are handed to the paragraph
+    let visible = app.messages.visible(
+        app.vertical_scroll,
+        inner.height.into(),
+        app.selected_line(),
+    );
+    let skipped_rows = visible.skipped_rows;
+    // the links are found again in the rendered lines
+    let links = match app.write_hyperlinks && !visible.links.is_empty() {
+        true => Some(visible.clone()),
+        false => None,
+    };
+    let message_field = Paragraph::new(visible.lines)
         .block(message_block)
-        .bg(Color::Black)
-        .scroll((app.vertical_scroll as u16, 0))
+        .fg(theme.text)
+        .bg(theme.background)
+        .scroll((skipped_rows as u16, 0))
         .wrap(Wrap { trim: true });
 
     frame.render_widget(message_field, chunks[0]);
 
+    // the reaction picker is shown above the bottom of the chat
+    let mut picker_area = None;
+    if let Some(picker) = &app.reaction_picker {
+        let line = picker.line();
+        let width = (line.width() as u16 + 2).min(inner.width);
+        let popup = Rect::new(
+            inner.x + (inner.width - width) / 2,
+            inner.bottom().saturating_sub(3).max(inner.y),
+            width,
+            3.min(inner.height),
+        );
+        let picker_block = Block::bordered()
+            .title(format!(" {} ", tr("reaction-picker")))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(style);
+        picker_area = Some(popup);
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(line)
+                .block(picker_block)
+                .fg(theme.text)
+                .bg(theme.background),
+            popup,
+        );
+    }
+
+    // the parts of the links below the picker are covered
+    app.hyperlinks = match links {
+        Some(visible) => hyperlinks::take(frame.buffer_mut(), inner, visible, picker_area),
+        None => Vec::new(),
+    };
+
     // Scrollbar
     app.vertical_scroll_state = app
         .vertical_scroll_state
-        .content_length(app.messages.lines.len());
+        .content_length(max_scroll)
+        .viewport_content_length(inner.height.into())
+        .position(app.vertical_scroll);
     frame.render_stateful_widget(
         Scrollbar::new(ScrollbarOrientation::VerticalRight)
             .begin_symbol(Some("↑"))
@@ -50,14 +143,41 @@ pub fn render_chat_tab(app: &mut App, frame: &mut Frame, area: Rect) {
     );
 
     // Eingabebereich (unten)
-    let input_block = Block::bordered()
-        .title(" Previous Input [Shift ←] | Last Input [Shift →]")
+    let hint = app.keymap.hint(
+        KeyScope::Chat,
+        &[
+            Action::Newline,
+            Action::HistoryPrevious,
+            Action::HistoryNext,
+            Action::EditLast,
+            Action::SelectPrevious,
+            Action::React,
+            Action::Reply,
+        ],
+    );
+    let mut input_block = Block::bordered()
+        .title(format!(" {hint} "))
         .title_alignment(Alignment::Right)
         .border_type(BorderType::Rounded)
         .border_style(style);
+    if let Some(reply_to) = &app.replying {
+        let text = tr_args(
+            "replying-to",
+            &[
+                ("name", reply_to.name.clone()),
+                ("excerpt", reply_to.excerpt.clone()),
+            ],
+        );
+        input_block = input_block.title_bottom(
+            Line::from(format!(" {text} "))
+                .fg(theme.info)
+                .left_aligned(),
+        );
+    }
 
     app.text_input.set_cursor_line_style(Style::default());
     app.text_input.set_block(input_block);
-    app.text_input.set_style(Style::new().bg(Color::Black));
+    app.text_input
+        .set_style(Style::new().fg(theme.text).bg(theme.background));
     app.text_input.render(chunks[1], frame.buffer_mut());
 }
@@ -5,7 +5,7 @@ use rand::Fill;
 use ratatui::{
     DefaultTerminal, Frame,
     crossterm::event::{self, Event, KeyCode, KeyEventKind},
-    layout::{Constraint, Layout, Margin, Rect},
+    layout::{Constraint, Layout, Margin, Position, Rect},
     style::{self, Color, Modifier, Style, Stylize},
     text::Text,
     widgets::{
@@ -14,10 +14,14 @@ use ratatui::{
     },
 };
 use serde::{Deserialize, Serialize};
-use style::palette::tailwind;
 
-use crate::types::{self, GROUP_HELP_FLAG, HELP_FLAG};
-use crate::{UI::app::App, types::JsonClient};
+use crate::UI::app::App;
+use crate::UI::keymap::KeyScope;
+use crate::UI::theme::{self, TableTheme};
+use rust_chat_client::i18n::catalog::{tr, tr_args};
+use rust_chat_client::plugins::plugin_registry::PluginInfo;
+use rust_chat_client::types::JsonClient;
+use rust_chat_client::types::{self, GROUP_HELP_FLAG, HELP_FLAG};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HelpItem {
@@ -48,21 +52,16 @@ pub struct HelpTable {
     pub fill_state: FillState,
     pub state: TableState,
     items: Vec<HelpItem>,
+    // help of the local plugins and scripts, shown before the help of the server
+    local_items: Vec<HelpItem>,
+    server_items: Vec<HelpItem>,
     longest_item_lens: (u16, u16),
     scroll_state: ScrollbarState,
     colors: TableColors,
-    color_index: usize,
+    // where the table was rendered last, used for mouse clicks
+    area: Rect,
 }
 
-const PALETTES: [tailwind::Palette; 4] = [
-    tailwind::BLUE,
-    tailwind::EMERALD,
-    tailwind::INDIGO,
-    tailwind::RED,
-];
-
-const INFO_TEXT: [&str; 1] = ["/help & /users & /group users are moved to the tabs"];
-
 const ITEM_HEIGHT: usize = 4;
 
 #[derive(Debug)]
@@ -80,18 +79,34 @@ pub struct TableColors {
 }
 
 impl TableColors {
-    pub const fn new(color: &tailwind::Palette) -> Self {
+    pub const fn new(theme: &TableTheme) -> Self {
         Self {
-            buffer_bg: tailwind::SLATE.c950,
-            header_bg: color.c900,
-            header_fg: tailwind::SLATE.c200,
-            row_fg: tailwind::SLATE.c200,
-            selected_row_style_fg: color.c400,
-            selected_column_style_fg: color.c400,
-            selected_cell_style_fg: color.c600,
-            normal_row_color: tailwind::SLATE.c950,
-            alt_row_color: tailwind::SLATE.c900,
-            footer_border_color: color.c400,
+            buffer_bg: theme.buffer_bg,
+            header_bg: theme.header_bg,
+            header_fg: theme.header_fg,
+            row_fg: theme.row_fg,
+            selected_row_style_fg: theme.selected_fg,
+            selected_column_style_fg: theme.selected_fg,
+            selected_cell_style_fg: theme.selected_cell_fg,
+            normal_row_color: theme.normal_row,
+            alt_row_color: theme.alt_row,
+            footer_border_color: theme.footer_border,
+        }
+    }
+}
+
+impl From<PluginInfo> for HelpItem {
+    fn from(info: PluginInfo) -> Self {
+        let mut description = info.description.clone();
+        if !info.aliases.is_empty() {
+            description.push_str(&format!(
+                " ({})",
+                tr_args("help-aliases", &[("aliases", info.aliases.join(", "))])
+            ));
+        }
+        HelpItem {
+            command: info.usage(),
+            description,
         }
     }
 }
@@ -108,21 +123,24 @@ impl HelpItem {
     pub fn command(&self) -> &str {
         &self.command
     }
+
+    // the command without its arguments
+    pub fn name(&self) -> &str {
+        self.command.split_whitespace().next().unwrap_or_default()
+    }
 }
 
 impl HelpTable {
     pub fn new() -> Self {
-        let data_vec = Vec::from([HelpItem {
-            command: String::from("/register {name}"),
-            description: String::from("registeres a client"),
-        }]);
         Self {
             state: TableState::default().with_selected(0),
-            longest_item_lens: constraint_len_calculator(&data_vec),
-            scroll_state: ScrollbarState::new((data_vec.len().saturating_sub(1)) * ITEM_HEIGHT),
-            colors: TableColors::new(&PALETTES[0]),
-            color_index: 0,
-            items: data_vec,
+            longest_item_lens: (0, 0),
+            scroll_state: ScrollbarState::new(0),
+            colors: TableColors::new(&theme::current().table),
+            area: Rect::default(),
+            items: Vec::new(),
+            local_items: Vec::new(),
+            server_items: Vec::new(),
             fill_state: FillState::new(),
         }
     }
@@ -134,6 +152,11 @@ impl HelpTable {
         false
     }
 
+    pub fn set_local_items(&mut self, items: Vec<HelpItem>) {
+        self.local_items = items;
+        self.merge_items();
+    }
+
     pub fn push_items(&mut self, items: Vec<HelpItem>, identifier: &str) {
         if self.filled() {
             return;
@@ -141,32 +164,54 @@ impl HelpTable {
         match identifier {
             HELP_FLAG => {
                 if !self.fill_state.help {
-                    self.items.remove(0);
                     self.fill_state.help = true;
-                    for item in items {
-                        self.items.push(item);
-                    }
+                    self.server_items.extend(items);
                 }
             }
             GROUP_HELP_FLAG => {
                 if !self.fill_state.group_help {
                     self.fill_state.group_help = true;
-                    for item in items {
-                        self.items.push(item);
-                    }
+                    self.server_items.extend(items);
                 }
             }
             _ => {}
         }
-        self.longest_item_lens = constraint_len_calculator(&self.items);
+        self.merge_items();
     }
 
     pub fn remove_items(&mut self) {
-        self.items = Vec::default();
+        self.server_items = Vec::default();
         self.fill_state = FillState::new();
+        self.merge_items();
+    }
+
+    // the local help wins if the server describes the same command
+    fn merge_items(&mut self) {
+        let local_commands: Vec<&str> = self.local_items.iter().map(HelpItem::name).collect();
+        let server_items = self
+            .server_items
+            .iter()
+            .filter(|item| !local_commands.contains(&item.name()));
+        self.items = self
+            .local_items
+            .iter()
+            .chain(server_items)
+            .cloned()
+            .collect();
+
+        self.longest_item_lens = constraint_len_calculator(&self.items);
+        self.scroll_state = self
+            .scroll_state
+            .content_length(self.items.len().saturating_sub(1) * ITEM_HEIGHT);
+        if self.state.selected().is_some_and(|i| i >= self.items.len()) {
+            self.state.select(Some(0));
+        }
     }
 
     pub fn next_row(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i >= self.items.len() - 1 {
@@ -182,6 +227,9 @@ impl HelpTable {
     }
 
     pub fn previous_row(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
@@ -196,25 +244,32 @@ impl HelpTable {
         self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
     }
 
-    pub fn next_column(&mut self) {
-        self.state.select_next_column();
+    pub fn select_row(&mut self, i: usize) {
+        self.state.select(Some(i));
+        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
     }
 
-    pub fn previous_column(&mut self) {
-        self.state.select_previous_column();
+    // returns the index of the item at the given position
+    pub fn row_at(&self, position: Position) -> Option<usize> {
+        if !self.area.contains(position) {
+            return None;
+        }
+        // the first line is the header
+        let visible_row = usize::from(position.y.checked_sub(self.area.y + 1)?) / ITEM_HEIGHT;
+        let i = self.state.offset() + visible_row;
+        (i < self.items.len()).then_some(i)
     }
 
-    pub fn next_color(&mut self) {
-        self.color_index = (self.color_index + 1) % PALETTES.len();
+    pub fn next_column(&mut self) {
+        self.state.select_next_column();
     }
 
-    pub fn previous_color(&mut self) {
-        let count = PALETTES.len();
-        self.color_index = (self.color_index + count - 1) % count;
+    pub fn previous_column(&mut self) {
+        self.state.select_previous_column();
     }
 
     pub fn set_colors(&mut self) {
-        self.colors = TableColors::new(&PALETTES[self.color_index]);
+        self.colors = TableColors::new(&theme::current().table);
     }
 
     pub fn render_help_tab(app: &mut App, frame: &mut Frame, area: Rect) {
@@ -225,10 +280,13 @@ impl HelpTable {
 
         app.help_table.render_table(frame, rects[0]);
         app.help_table.render_scrollbar(frame, rects[0]);
-        app.help_table.render_footer(frame, rects[1]);
+        let mut footer = Text::from(tr("help-info"));
+        footer.push_line(app.keymap.footer(KeyScope::Help));
+        app.help_table.render_footer(footer, frame, rects[1]);
     }
 
     fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
         let header_style = Style::default()
             .fg(self.colors.header_fg)
             .bg(self.colors.header_bg);
@@ -240,7 +298,7 @@ impl HelpTable {
             .add_modifier(Modifier::REVERSED)
             .fg(self.colors.selected_cell_style_fg);
 
-        let header = ["Command", "Description"]
+        let header = [tr("header-command"), tr("header-description")]
             .into_iter()
             .map(Cell::from)
             .collect::<Row>()
@@ -296,8 +354,8 @@ impl HelpTable {
         );
     }
 
-    fn render_footer(&self, frame: &mut Frame, area: Rect) {
-        let info_footer = Paragraph::new(Text::from_iter(INFO_TEXT))
+    fn render_footer(&self, text: Text<'static>, frame: &mut Frame, area: Rect) {
+        let info_footer = Paragraph::new(text)
             .style(
                 Style::new()
                     .fg(self.colors.row_fg)
@@ -0,0 +1,257 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Text,
+    widgets::{
+        Block, BorderType, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table, TableState,
+    },
+};
+
+use crate::UI::app::App;
+use crate::UI::keymap::KeyScope;
+use crate::UI::theme::{self, TableTheme};
+use rust_chat_client::i18n::catalog::tr;
+
+// older mentions are dropped
+const MAX_MENTIONS: usize = 500;
+
+// a message which mentioned the user
+#[derive(Debug, Clone)]
+pub struct MentionItem {
+    pub name: String,
+    // where the message was sent, the group, everyone or private
+    pub place: String,
+    pub text: String,
+    // line of the message in the chat
+    pub line_id: usize,
+    // empty for clients without message ids
+    pub message_id: String,
+}
+
+#[derive(Debug)]
+pub struct TableColors {
+    buffer_bg: Color,
+    header_bg: Color,
+    header_fg: Color,
+    row_fg: Color,
+    selected_row_style_fg: Color,
+    normal_row_color: Color,
+    alt_row_color: Color,
+    footer_border_color: Color,
+}
+
+impl TableColors {
+    pub const fn new(theme: &TableTheme) -> Self {
+        Self {
+            buffer_bg: theme.buffer_bg,
+            header_bg: theme.header_bg,
+            header_fg: theme.header_fg,
+            row_fg: theme.row_fg,
+            selected_row_style_fg: theme.selected_fg,
+            normal_row_color: theme.normal_row,
+            alt_row_color: theme.alt_row,
+            footer_border_color: theme.footer_border,
+        }
+    }
+}
+
+// MentionsTable lists the messages of all conversations which mentioned the user,
+// the newest one first
+#[derive(Debug)]
+pub struct MentionsTable {
+    pub state: TableState,
+    items: Vec<MentionItem>,
+    // mentions which arrived while the tab wasn't shown
+    pub unread: usize,
+    scroll_state: ScrollbarState,
+    colors: TableColors,
+    // where the table was rendered last, used for mouse clicks
+    area: Rect,
+}
+
+impl MentionsTable {
+    pub fn new() -> Self {
+        Self {
+            state: TableState::default().with_selected(0),
+            items: Vec::new(),
+            unread: 0,
+            scroll_state: ScrollbarState::new(0),
+            colors: TableColors::new(&theme::current().table),
+            area: Rect::default(),
+        }
+    }
+
+    pub fn push(&mut self, item: MentionItem) {
+        self.items.insert(0, item);
+        self.items.truncate(MAX_MENTIONS);
+        self.unread += 1;
+        self.scroll_state = self.scroll_state.content_length(self.items.len());
+        // the selection stays on the same mention
+        if let Some(i) = self.state.selected()
+            && i > 0
+        {
+            self.select_row((i + 1).min(self.items.len() - 1));
+        }
+    }
+
+    pub fn selected(&self) -> Option<&MentionItem> {
+        self.items.get(self.state.selected()?)
+    }
+
+    pub fn next_row(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.items.len() => i + 1,
+            _ => 0,
+        };
+        self.select_row(i);
+    }
+
+    pub fn previous_row(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.items.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.select_row(i);
+    }
+
+    pub fn select_row(&mut self, i: usize) {
+        self.state.select(Some(i));
+        self.scroll_state = self.scroll_state.position(i);
+    }
+
+    // returns the index of the item at the given position
+    pub fn row_at(&self, position: Position) -> Option<usize> {
+        if !self.area.contains(position) {
+            return None;
+        }
+        // the first line is the header
+        let visible_row = usize::from(position.y.checked_sub(self.area.y + 1)?);
+        let i = self.state.offset() + visible_row;
+        (i < self.items.len()).then_some(i)
+    }
+
+    pub fn set_colors(&mut self) {
+        self.colors = TableColors::new(&theme::current().table);
+    }
+
+    pub fn render_mentions_tab(app: &mut App, frame: &mut Frame, area: Rect) {
+        let vertical = &Layout::vertical([Constraint::Min(5), Constraint::Length(4)]);
+        let rects = vertical.split(area);
+
+        app.mentions_table.set_colors();
+
+        app.mentions_table.render_table(frame, rects[0]);
+        app.mentions_table.render_scrollbar(frame, rects[0]);
+        let footer = Text::from(app.keymap.footer(KeyScope::Mentions));
+        app.mentions_table.render_footer(footer, frame, rects[1]);
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
+        if self.items.is_empty() {
+            let empty = Paragraph::new(tr("mentions-empty"))
+                .centered()
+                .fg(self.colors.row_fg)
+                .bg(self.colors.buffer_bg);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let header_style = Style::default()
+            .fg(self.colors.header_fg)
+            .bg(self.colors.header_bg);
+        let selected_row_style = Style::default()
+            .add_modifier(Modifier::REVERSED)
+            .fg(self.colors.selected_row_style_fg);
+
+        let header = [tr("header-name"), tr("header-place"), tr("header-message")]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(header_style)
+            .height(1);
+        let rows = self.items.iter().enumerate().map(|(i, item)| {
+            let color = match i % 2 {
+                0 => self.colors.normal_row_color,
+                _ => self.colors.alt_row_color,
+            };
+            // a row has one line, multi-line messages are joined
+            [
+                item.name.clone(),
+                item.place.clone(),
+                item.text.lines().collect::<Vec<_>>().join(" "),
+            ]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(Style::new().fg(self.colors.row_fg).bg(color))
+        });
+        let name_len = self
+            .items
+            .iter()
+            .map(|item| item.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let place_len = self
+            .items
+            .iter()
+            .map(|item| item.place.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let t = Table::new(
+            rows,
+            [
+                // + 1 is for padding.
+                Constraint::Length(name_len as u16 + 1),
+                Constraint::Length(place_len as u16 + 1),
+                Constraint::Min(10),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(selected_row_style)
+        .highlight_symbol(" █ ")
+        .bg(self.colors.buffer_bg)
+        .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(t, area, &mut self.state);
+    }
+
+    fn render_scrollbar(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_stateful_widget(
+            Scrollbar::default()
+                .orientation(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None),
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 1,
+            }),
+            &mut self.scroll_state,
+        );
+    }
+
+    fn render_footer(&self, text: Text<'static>, frame: &mut Frame, area: Rect) {
+        let info_footer = Paragraph::new(text)
+            .style(
+                Style::new()
+                    .fg(self.colors.row_fg)
+                    .bg(self.colors.buffer_bg),
+            )
+            .centered()
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Double)
+                    .border_style(Style::new().fg(self.colors.footer_border_color)),
+            );
+        frame.render_widget(info_footer, area);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod chat;
 pub mod help;
+pub mod mentions;
 pub mod tabs;
 pub mod users;
@@ -8,19 +8,21 @@ use ratatui::{style::Color, text::Line, widgets::Paragraph};
 use strum_macros::{Display, EnumIter, FromRepr};
 
 use crate::UI::app::App;
-use crate::UI::tabs::{chat, help, users};
-use crate::types::BLUE_COLOR;
-use crate::types::TURKIS_COLOR;
+use crate::UI::tabs::{chat, help, mentions, users};
+use crate::UI::theme::Theme;
+use rust_chat_client::i18n::catalog::tr;
 
 #[derive(Default, Clone, Copy, Display, FromRepr, EnumIter, Debug)]
 pub enum SelectedTab {
     #[default]
     #[strum(to_string = "Chat")]
     Chat = 0,
+    #[strum(to_string = "Mentions")]
+    Mentions = 1,
     #[strum(to_string = "Users")]
-    Users = 1,
+    Users = 2,
     #[strum(to_string = "Help")]
-    Help = 2,
+    Help = 3,
     // #[strum(to_string = "Tab 4")]
     // Tab4 = 3,
 }
@@ -30,6 +32,7 @@ impl SelectedTab {
         // in a real app these might be separate widgets
         match self {
             Self::Chat => self.render_tab0(app, frame, area),
+            Self::Mentions => mentions::MentionsTable::render_mentions_tab(app, frame, area),
             Self::Users => self.render_tab1(app, frame, area),
             Self::Help => self.render_tab2(app, frame, area),
             // Self::Tab4 => self.render_tab3(app, frame, area),
@@ -50,7 +53,8 @@ impl SelectedTab {
     }
 
     pub fn title(self) -> Line<'static> {
-        Line::from(format!("  {self}  "))
+        let key = format!("tab-{}", self.to_string().to_lowercase());
+        Line::from(format!("  {}  ", tr(&key)))
     }
 
     fn render_tab0(self, app: &mut App, frame: &mut Frame, area: Rect) {
@@ -67,28 +71,17 @@ impl SelectedTab {
 
     fn render_tab3(self, app: &mut App, frame: &mut Frame, area: Rect) {}
 
-    pub const fn palette(self) -> Palette {
-        match self {
-            Self::Users => Palette {
-                fg: BLUE_COLOR, // blue
-                bg: Color::Black,
-                border: BLUE_COLOR,
-            },
-            // Self::Tab4 => Palette {
-            //     fg: Color::Indexed(63), // purple
-            //     bg: Color::Black,
-            //     border: Color::Indexed(63),
-            // },
-            Self::Chat => Palette {
-                fg: TURKIS_COLOR, // turkis
-                bg: Color::Black,
-                border: TURKIS_COLOR,
-            },
-            Self::Help => Palette {
-                fg: Color::Rgb(191, 53, 53), // orange
-                bg: Color::Black,
-                border: Color::Rgb(191, 53, 53),
-            },
+    pub fn palette(self, theme: &Theme) -> Palette {
+        let color = match self {
+            Self::Chat => theme.chat_tab,
+            Self::Mentions => theme.mentions_tab,
+            Self::Users => theme.users_tab,
+            Self::Help => theme.help_tab,
+        };
+        Palette {
+            fg: color,
+            bg: theme.background,
+            border: color,
         }
     }
 }
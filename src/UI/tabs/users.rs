@@ -4,18 +4,22 @@ use itertools::Itertools;
 use ratatui::{
     DefaultTerminal, Frame,
     crossterm::event::{self, Event, KeyCode, KeyEventKind},
-    layout::{Constraint, Layout, Margin, Rect},
+    layout::{Constraint, Layout, Margin, Position, Rect},
     style::{self, Color, Modifier, Style, Stylize},
-    text::Text,
+    text::{Line, Text},
     widgets::{
         Block, BorderType, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
         ScrollbarState, Table, TableState,
     },
 };
-use style::palette::tailwind;
 
-use crate::types;
-use crate::{UI::app::App, types::JsonClient};
+use crate::UI::app::App;
+use crate::UI::keymap::KeyScope;
+use crate::UI::theme::{self, TableTheme};
+use crate::UI::user_interface::sender_spans;
+use rust_chat_client::i18n::catalog::tr;
+use rust_chat_client::types;
+use rust_chat_client::types::JsonClient;
 
 #[derive(Debug)]
 pub struct UsersTable {
@@ -24,19 +28,12 @@ pub struct UsersTable {
     longest_item_lens: (u16, u16, u16),
     scroll_state: ScrollbarState,
     colors: TableColors,
-    color_index: usize,
+    // initials in front of the names, as in the chat
+    pub name_badges: bool,
+    // where the table was rendered last, used for mouse clicks
+    area: Rect,
 }
 
-const PALETTES: [tailwind::Palette; 4] = [
-    tailwind::BLUE,
-    tailwind::EMERALD,
-    tailwind::INDIGO,
-    tailwind::RED,
-];
-
-const INFO_TEXT: [&str; 1] =
-    ["[↑] move up | [↓] move down | [u] refresh users | [g] only group users"];
-
 const ITEM_HEIGHT: usize = 4;
 
 #[derive(Debug)]
@@ -54,40 +51,22 @@ pub struct TableColors {
 }
 
 impl TableColors {
-    pub const fn new(color: &tailwind::Palette) -> Self {
+    pub const fn new(theme: &TableTheme) -> Self {
         Self {
-            buffer_bg: tailwind::SLATE.c950,
-            header_bg: color.c900,
-            header_fg: tailwind::SLATE.c200,
-            row_fg: tailwind::SLATE.c200,
-            selected_row_style_fg: color.c400,
-            selected_column_style_fg: color.c400,
-            selected_cell_style_fg: color.c600,
-            normal_row_color: tailwind::SLATE.c950,
-            alt_row_color: tailwind::SLATE.c900,
-            footer_border_color: color.c400,
+            buffer_bg: theme.buffer_bg,
+            header_bg: theme.header_bg,
+            header_fg: theme.header_fg,
+            row_fg: theme.row_fg,
+            selected_row_style_fg: theme.selected_fg,
+            selected_column_style_fg: theme.selected_fg,
+            selected_cell_style_fg: theme.selected_cell_fg,
+            normal_row_color: theme.normal_row,
+            alt_row_color: theme.alt_row,
+            footer_border_color: theme.footer_border,
         }
     }
 }
 
-impl JsonClient {
-    const fn ref_array(&self) -> [&String; 3] {
-        [&self.name, &self.call_state, &self.group_name]
-    }
-
-    pub fn name(&self) -> &str {
-        &self.name
-    }
-
-    pub fn call_state(&self) -> &str {
-        &self.call_state
-    }
-
-    pub fn group_name(&self) -> &str {
-        &self.group_name
-    }
-}
-
 impl UsersTable {
     pub fn new() -> Self {
         let data_vec = vec![types::dummy_json_client()];
@@ -95,8 +74,9 @@ impl UsersTable {
             state: TableState::default().with_selected(0),
             longest_item_lens: constraint_len_calculator(&data_vec),
             scroll_state: ScrollbarState::new((data_vec.len().saturating_sub(1)) * ITEM_HEIGHT),
-            colors: TableColors::new(&PALETTES[0]),
-            color_index: 0,
+            colors: TableColors::new(&theme::current().table),
+            name_badges: false,
+            area: Rect::default(),
             items: data_vec,
         }
     }
@@ -104,7 +84,7 @@ impl UsersTable {
     pub fn update_items(&mut self, mut items: Vec<JsonClient>, own_client: JsonClient) {
         for item in items.iter_mut() {
             if item.group_name.is_empty() {
-                item.group_name = String::from("no group");
+                item.group_name = tr("no-group");
             }
         }
         self.items = items;
@@ -147,25 +127,32 @@ impl UsersTable {
         self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
     }
 
-    pub fn next_column(&mut self) {
-        self.state.select_next_column();
+    pub fn select_row(&mut self, i: usize) {
+        self.state.select(Some(i));
+        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
     }
 
-    pub fn previous_column(&mut self) {
-        self.state.select_previous_column();
+    // returns the index of the item at the given position
+    pub fn row_at(&self, position: Position) -> Option<usize> {
+        if !self.area.contains(position) {
+            return None;
+        }
+        // the first line is the header
+        let visible_row = usize::from(position.y.checked_sub(self.area.y + 1)?) / ITEM_HEIGHT;
+        let i = self.state.offset() + visible_row;
+        (i < self.items.len()).then_some(i)
     }
 
-    pub fn next_color(&mut self) {
-        self.color_index = (self.color_index + 1) % PALETTES.len();
+    pub fn next_column(&mut self) {
+        self.state.select_next_column();
     }
 
-    pub fn previous_color(&mut self) {
-        let count = PALETTES.len();
-        self.color_index = (self.color_index + count - 1) % count;
+    pub fn previous_column(&mut self) {
+        self.state.select_previous_column();
     }
 
     pub fn set_colors(&mut self) {
-        self.colors = TableColors::new(&PALETTES[self.color_index]);
+        self.colors = TableColors::new(&theme::current().table);
     }
 
     pub fn render_users_tab(app: &mut App, frame: &mut Frame, area: Rect) {
@@ -176,10 +163,12 @@ impl UsersTable {
 
         app.users_table.render_table(frame, rects[0]);
         app.users_table.render_scrollbar(frame, rects[0]);
-        app.users_table.render_footer(frame, rects[1]);
+        let footer = Text::from(app.keymap.footer(KeyScope::Users));
+        app.users_table.render_footer(footer, frame, rects[1]);
     }
 
     fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
         let header_style = Style::default()
             .fg(self.colors.header_fg)
             .bg(self.colors.header_bg);
@@ -191,7 +180,7 @@ impl UsersTable {
             .add_modifier(Modifier::REVERSED)
             .fg(self.colors.selected_cell_style_fg);
 
-        let header = ["Name", "Call", "Group Name"]
+        let header = [tr("header-name"), tr("header-call"), tr("header-group")]
             .into_iter()
             .map(Cell::from)
             .collect::<Row>()
@@ -202,19 +191,31 @@ impl UsersTable {
                 0 => self.colors.normal_row_color,
                 _ => self.colors.alt_row_color,
             };
-            let item = data.ref_array();
-            item.into_iter()
-                .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
-                .collect::<Row>()
-                .style(Style::new().fg(self.colors.row_fg).bg(color))
-                .height(4)
+            // the name has the same color as in the chat
+            let name = Line::from(sender_spans(
+                data.name.clone(),
+                &data.client_id,
+                self.name_badges,
+            ));
+            let [_, call_state, group_name] = data.ref_array();
+            [
+                Cell::from(Text::from(vec![Line::default(), name])),
+                Cell::from(Text::from(format!("\n{call_state}\n"))),
+                Cell::from(Text::from(format!("\n{group_name}\n"))),
+            ]
+            .into_iter()
+            .collect::<Row>()
+            .style(Style::new().fg(self.colors.row_fg).bg(color))
+            .height(4)
         });
         let bar = " █ ";
+        // the badge has two letters with padding and a space before the name
+        let badge_len = if self.name_badges { 5 } else { 0 };
         let t = Table::new(
             rows,
             [
                 // + 1 is for padding.
-                Constraint::Length(self.longest_item_lens.0 + 1),
+                Constraint::Length(self.longest_item_lens.0 + badge_len + 1),
                 Constraint::Min(self.longest_item_lens.1 + 1),
                 Constraint::Min(self.longest_item_lens.2),
             ],
@@ -248,8 +249,8 @@ impl UsersTable {
         );
     }
 
-    fn render_footer(&self, frame: &mut Frame, area: Rect) {
-        let info_footer = Paragraph::new(Text::from_iter(INFO_TEXT))
+    fn render_footer(&self, text: Text<'static>, frame: &mut Frame, area: Rect) {
+        let info_footer = Paragraph::new(text)
             .style(
                 Style::new()
                     .fg(self.colors.row_fg)
@@ -0,0 +1,65 @@
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
+};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::supports_keyboard_enhancement;
+use std::io::stdout;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// set while the keyboard enhancement flags are pushed, so they are popped only once
+static KEYS_ENHANCED: AtomicBool = AtomicBool::new(false);
+
+// TerminalGuard restores the terminal when it is dropped. A panic hook restores
+// it as well, before the panic is printed.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn init() -> color_eyre::Result<(DefaultTerminal, TerminalGuard)> {
+        let terminal = ratatui::init();
+        let guard = TerminalGuard;
+        // ratatui's hook leaves the alternate screen, the modes are disabled before
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            disable_modes();
+            hook(info);
+        }));
+
+        execute!(
+            stdout(),
+            EnableMouseCapture,
+            EnableFocusChange,
+            EnableBracketedPaste
+        )?;
+        // terminals with the kitty keyboard protocol tell shift+enter apart from enter
+        if supports_keyboard_enhancement().unwrap_or(false) {
+            execute!(
+                stdout(),
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+            )?;
+            KEYS_ENHANCED.store(true, Ordering::SeqCst);
+        }
+        Ok((terminal, guard))
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        disable_modes();
+        ratatui::restore();
+    }
+}
+
+fn disable_modes() {
+    if KEYS_ENHANCED.swap(false, Ordering::SeqCst) {
+        let _ = execute!(stdout(), PopKeyboardEnhancementFlags);
+    }
+    let _ = execute!(
+        stdout(),
+        DisableMouseCapture,
+        DisableFocusChange,
+        DisableBracketedPaste
+    );
+}
@@ -0,0 +1,456 @@
+use ratatui::style::Color;
+use ratatui::style::palette::tailwind;
+use rust_chat_client::config::settings;
+use rust_chat_client::helper;
+use rust_chat_client::types::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+
+pub const DEFAULT_THEME: &str = "dark";
+pub const PRESETS: [&str; 3] = ["dark", "light", "high-contrast"];
+
+// contrast ratio which names need against the background, as for normal text in WCAG
+const MIN_CONTRAST: f64 = 4.5;
+// colors for the names of themes which only use the base colors
+const BASE_NAME_COLORS: [Color; 12] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+// the theme all span helpers and renderers read from
+static CURRENT: LazyLock<RwLock<Theme>> = LazyLock::new(|| RwLock::new(Theme::dark()));
+
+// Theme contains all colors of the UI.
+// Custom themes are TOML files in the themes directory of the config dir,
+// they may set `base = "<preset>"` and only override some colors.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub background: Color,
+    pub text: Color,
+    pub dim: Color,
+    pub error: Color,
+    pub info: Color,
+    pub accent: Color,
+    pub name: Color,
+    pub success: Color,
+    pub border: Color,
+    pub chat_tab: Color,
+    pub users_tab: Color,
+    pub help_tab: Color,
+    pub mentions_tab: Color,
+    // mentions of the own name
+    pub mention: Color,
+    pub link: Color,
+    // inline code and code blocks in messages
+    pub code: Color,
+    pub code_bg: Color,
+    // the names of the users get colors of the 16 base colors instead of rgb colors
+    pub base_name_colors: bool,
+    pub table: TableTheme,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TableTheme {
+    pub buffer_bg: Color,
+    pub header_bg: Color,
+    pub header_fg: Color,
+    pub row_fg: Color,
+    pub selected_fg: Color,
+    pub selected_cell_fg: Color,
+    pub normal_row: Color,
+    pub alt_row: Color,
+    pub footer_border: Color,
+}
+
+impl Theme {
+    pub const fn dark() -> Theme {
+        Theme {
+            background: Color::Black,
+            text: Color::Reset,
+            dim: tailwind::SLATE.c500,
+            error: Color::Rgb(191, 53, 53),
+            info: tailwind::BLUE.c400,
+            accent: tailwind::PURPLE.c950,
+            name: Color::Rgb(53, 191, 188),
+            success: Color::Rgb(62, 138, 41),
+            border: tailwind::CYAN.c800,
+            chat_tab: Color::Rgb(53, 191, 188),
+            users_tab: tailwind::BLUE.c400,
+            help_tab: Color::Rgb(191, 53, 53),
+            mentions_tab: tailwind::AMBER.c400,
+            mention: tailwind::AMBER.c400,
+            link: tailwind::SKY.c400,
+            code: tailwind::ORANGE.c300,
+            code_bg: tailwind::SLATE.c800,
+            base_name_colors: false,
+            table: TableTheme {
+                buffer_bg: tailwind::SLATE.c950,
+                header_bg: tailwind::BLUE.c900,
+                header_fg: tailwind::SLATE.c200,
+                row_fg: tailwind::SLATE.c200,
+                selected_fg: tailwind::BLUE.c400,
+                selected_cell_fg: tailwind::BLUE.c600,
+                normal_row: tailwind::SLATE.c950,
+                alt_row: tailwind::SLATE.c900,
+                footer_border: tailwind::BLUE.c400,
+            },
+        }
+    }
+
+    pub const fn light() -> Theme {
+        Theme {
+            background: Color::White,
+            text: Color::Black,
+            dim: tailwind::SLATE.c500,
+            error: tailwind::RED.c700,
+            info: tailwind::BLUE.c700,
+            accent: tailwind::PURPLE.c700,
+            name: tailwind::TEAL.c700,
+            success: tailwind::GREEN.c700,
+            border: tailwind::CYAN.c600,
+            chat_tab: tailwind::TEAL.c700,
+            users_tab: tailwind::BLUE.c700,
+            help_tab: tailwind::RED.c700,
+            mentions_tab: tailwind::AMBER.c700,
+            mention: tailwind::AMBER.c700,
+            link: tailwind::SKY.c700,
+            code: tailwind::ORANGE.c800,
+            code_bg: tailwind::SLATE.c100,
+            base_name_colors: false,
+            table: TableTheme {
+                buffer_bg: tailwind::SLATE.c50,
+                header_bg: tailwind::BLUE.c200,
+                header_fg: tailwind::SLATE.c900,
+                row_fg: tailwind::SLATE.c900,
+                selected_fg: tailwind::BLUE.c700,
+                selected_cell_fg: tailwind::BLUE.c500,
+                normal_row: tailwind::SLATE.c50,
+                alt_row: tailwind::SLATE.c200,
+                footer_border: tailwind::BLUE.c600,
+            },
+        }
+    }
+
+    // only uses the 16 base colors, so it also works on limited terminals
+    pub const fn high_contrast() -> Theme {
+        Theme {
+            background: Color::Black,
+            text: Color::White,
+            dim: Color::Gray,
+            error: Color::LightRed,
+            info: Color::LightCyan,
+            accent: Color::LightMagenta,
+            name: Color::Yellow,
+            success: Color::LightGreen,
+            border: Color::White,
+            chat_tab: Color::Yellow,
+            users_tab: Color::LightCyan,
+            help_tab: Color::LightRed,
+            mentions_tab: Color::LightMagenta,
+            mention: Color::LightYellow,
+            link: Color::LightBlue,
+            code: Color::Cyan,
+            code_bg: Color::DarkGray,
+            base_name_colors: true,
+            table: TableTheme {
+                buffer_bg: Color::Black,
+                header_bg: Color::White,
+                header_fg: Color::Black,
+                row_fg: Color::White,
+                selected_fg: Color::Yellow,
+                selected_cell_fg: Color::LightYellow,
+                normal_row: Color::Black,
+                alt_row: Color::DarkGray,
+                footer_border: Color::White,
+            },
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    // Load returns a preset or reads <config dir>/themes/<name>.toml
+    pub fn load(name: &str) -> Result<Theme, ConfigError> {
+        if let Some(theme) = Theme::preset(name) {
+            return Ok(theme);
+        }
+        let dir = settings::config_dir()
+            .map(|dir| dir.join("themes"))
+            .ok_or_else(|| ConfigError::Io(format!("Theme '{name}' not found")))?;
+        Theme::load_from(&dir, name)
+    }
+
+    // Load_from reads <dir>/<name>.toml, presets are not looked up
+    pub fn load_from(dir: &Path, name: &str) -> Result<Theme, ConfigError> {
+        let path = dir.join(format!("{name}.toml"));
+        let raw = std::fs::read_to_string(&path)
+            .map_err(|e| ConfigError::Io(format!("Theme '{name}' not found: {e}")))?;
+        let mut overrides: toml::Table = toml::from_str(&raw)
+            .map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))?;
+
+        let base_name = match overrides.remove("base") {
+            Some(toml::Value::String(base)) => base,
+            _ => DEFAULT_THEME.to_string(),
+        };
+        let base = Theme::preset(&base_name)
+            .ok_or_else(|| ConfigError::Parse(format!("Unknown base theme '{base_name}'")))?;
+        let mut table = toml::Table::try_from(base)
+            .map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))?;
+        merge(&mut table, overrides);
+
+        table
+            .try_into()
+            .map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))
+    }
+
+    // Name_color returns the color of a user, it is the same on every client.
+    // Colors with a low contrast to the background are avoided.
+    // Lines without a client id, e.g. from the server, use the name color.
+    pub fn name_color(&self, client_id: &str) -> Color {
+        if client_id.is_empty() {
+            return self.name;
+        }
+        let hash = helper::stable_hash(client_id);
+        let background = luminance(self.background);
+        if self.base_name_colors {
+            let colors: Vec<Color> = BASE_NAME_COLORS
+                .into_iter()
+                .filter(|c| contrast(luminance(*c), background) >= MIN_CONTRAST)
+                .collect();
+            return match colors.len() {
+                0 => self.name,
+                len => colors[(hash % len as u64) as usize],
+            };
+        }
+
+        let hue = (hash % 360) as f64;
+        // names are lighter than the background on dark themes and darker on light ones
+        let dark = contrast(background, 0.0) < contrast(background, 1.0);
+        let step = if dark { 0.05 } else { -0.05 };
+        let mut lightness = if dark { 0.65 } else { 0.4 };
+        let mut color = hsl(hue, 0.65, lightness);
+        while contrast(luminance(color), background) < MIN_CONTRAST
+            && (0.1..=0.9).contains(&(lightness + step))
+        {
+            lightness += step;
+            color = hsl(hue, 0.65, lightness);
+        }
+        color
+    }
+}
+
+// rgb values of a color, the base colors as xterm shows them
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Black => Some((0, 0, 0)),
+        Color::Red => Some((205, 0, 0)),
+        Color::Green => Some((0, 205, 0)),
+        Color::Yellow => Some((205, 205, 0)),
+        Color::Blue => Some((0, 0, 238)),
+        Color::Magenta => Some((205, 0, 205)),
+        Color::Cyan => Some((0, 205, 205)),
+        Color::Gray => Some((229, 229, 229)),
+        Color::DarkGray => Some((127, 127, 127)),
+        Color::LightRed => Some((255, 0, 0)),
+        Color::LightGreen => Some((0, 255, 0)),
+        Color::LightYellow => Some((255, 255, 0)),
+        Color::LightBlue => Some((92, 92, 255)),
+        Color::LightMagenta => Some((255, 0, 255)),
+        Color::LightCyan => Some((0, 255, 255)),
+        Color::White => Some((255, 255, 255)),
+        _ => None,
+    }
+}
+
+// relative luminance, unknown colors like the default background count as black
+fn luminance(color: Color) -> f64 {
+    let Some((r, g, b)) = rgb(color) else {
+        return 0.0;
+    };
+    let channel = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+fn contrast(a: f64, b: f64) -> f64 {
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+// converts hue (0-360), saturation and lightness (0-1) to a rgb color
+fn hsl(hue: f64, saturation: f64, lightness: f64) -> Color {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u16 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    Color::Rgb(channel(r), channel(g), channel(b))
+}
+
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(table)) => {
+                merge(base_table, table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+// lists the presets and all theme files in the themes directory
+pub fn available() -> Vec<String> {
+    let mut themes: Vec<String> = PRESETS.iter().map(|p| p.to_string()).collect();
+    let dir = settings::config_dir().map(|dir| dir.join("themes"));
+    if let Some(Ok(entries)) = dir.map(std::fs::read_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "toml")
+                && let Some(stem) = path.file_stem()
+            {
+                themes.push(stem.to_string_lossy().to_string());
+            }
+        }
+    }
+    themes
+}
+
+pub fn current() -> Theme {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn set(theme: Theme) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // a themes directory with one theme file
+    fn themes_dir(test: &str, name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("chat-themes-{test}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{name}.toml")), content).unwrap();
+        dir
+    }
+
+    #[test]
+    fn presets_are_found_by_name() {
+        for name in PRESETS {
+            assert!(Theme::preset(name).is_some(), "{name}");
+        }
+        assert_eq!(Theme::preset("light"), Some(Theme::light()));
+        assert_eq!(Theme::preset("solarized"), None);
+        assert_eq!(
+            Theme::load("high-contrast").unwrap(),
+            Theme::high_contrast()
+        );
+    }
+
+    #[test]
+    fn themes_override_their_base() {
+        let dir = themes_dir(
+            "base",
+            "paper",
+            "base = \"light\"\naccent = \"Red\"\n[table]\nheader_bg = \"#102030\"\n",
+        );
+        let theme = Theme::load_from(&dir, "paper").unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+
+        let light = Theme::light();
+        assert_eq!(theme.accent, Color::Red);
+        assert_eq!(theme.table.header_bg, Color::Rgb(0x10, 0x20, 0x30));
+        // everything else is taken from the base, also in nested tables
+        assert_eq!(theme.background, light.background);
+        assert_eq!(theme.table.header_fg, light.table.header_fg);
+    }
+
+    #[test]
+    fn themes_without_base_extend_the_default() {
+        let dir = themes_dir("default", "red", "name = \"Red\"\n");
+        let theme = Theme::load_from(&dir, "red").unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+
+        assert_eq!(
+            theme,
+            Theme {
+                name: Color::Red,
+                ..Theme::preset(DEFAULT_THEME).unwrap()
+            }
+        );
+    }
+
+    #[test]
+    fn name_colors_of_the_presets_are_readable() {
+        for name in PRESETS {
+            for base_name_colors in [false, true] {
+                let theme = Theme {
+                    base_name_colors,
+                    ..Theme::preset(name).unwrap()
+                };
+                let background = luminance(theme.background);
+                for i in 0..1000 {
+                    let color = theme.name_color(&format!("client-{i}"));
+                    let ratio = contrast(luminance(color), background);
+                    assert!(
+                        ratio >= MIN_CONTRAST,
+                        "{name} ({base_name_colors}): {color} has a contrast of {ratio:.2}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn broken_themes_are_rejected() {
+        let dir = themes_dir("broken", "unknown-base", "base = \"sepia\"\n");
+        std::fs::write(dir.join("bad-color.toml"), "text = \"not a color\"\n").unwrap();
+        std::fs::write(dir.join("invalid.toml"), "text = ").unwrap();
+
+        for name in ["unknown-base", "bad-color", "invalid"] {
+            assert!(
+                matches!(Theme::load_from(&dir, name), Err(ConfigError::Parse(_))),
+                "{name}"
+            );
+        }
+        assert!(matches!(
+            Theme::load_from(&dir, "missing"),
+            Err(ConfigError::Io(_))
+        ));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
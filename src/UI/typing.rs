@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// a typing notification is sent at most this often per conversation
+const SEND_INTERVAL: Duration = Duration::from_secs(3);
+// a user is shown as typing until no notification arrived for this long
+const EXPIRY: Duration = Duration::from_secs(6);
+
+// Typing throttles the own typing notifications and keeps the users who are typing
+#[derive(Debug, Default)]
+pub struct Typing {
+    // conversation (client id or group id) -> last sent notification
+    sent: HashMap<String, Instant>,
+    // name -> last received notification
+    users: Vec<(String, Instant)>,
+}
+
+impl Typing {
+    // returns true if a notification for the conversation is due
+    pub fn should_send(&mut self, conversation: &str) -> bool {
+        self.should_send_at(conversation, Instant::now())
+    }
+
+    // the time is passed in, so tests don't have to wait
+    fn should_send_at(&mut self, conversation: &str, now: Instant) -> bool {
+        match self.sent.get(conversation) {
+            Some(last) if now.duration_since(*last) < SEND_INTERVAL => false,
+            _ => {
+                self.sent.insert(conversation.to_string(), now);
+                true
+            }
+        }
+    }
+
+    // after sending, the next input notifies again immediately
+    pub fn message_sent(&mut self) {
+        self.sent.clear();
+    }
+
+    pub fn received(&mut self, name: &str) {
+        self.received_at(name, Instant::now());
+    }
+
+    fn received_at(&mut self, name: &str, now: Instant) {
+        match self.users.iter_mut().find(|(n, _)| n == name) {
+            Some((_, last)) => *last = now,
+            None => self.users.push((name.to_string(), now)),
+        }
+    }
+
+    // a message of the user ends the typing
+    pub fn stopped(&mut self, name: &str) {
+        self.users.retain(|(n, _)| n != name);
+    }
+
+    // removes expired users, returns true if someone was removed
+    pub fn expire(&mut self) -> bool {
+        self.expire_at(Instant::now())
+    }
+
+    fn expire_at(&mut self, now: Instant) -> bool {
+        let before = self.users.len();
+        self.users
+            .retain(|(_, last)| now.duration_since(*last) < EXPIRY);
+        self.users.len() != before
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.users.iter().map(|(name, _)| name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn notifications_are_throttled_per_conversation() {
+        let mut typing = Typing::default();
+        let start = Instant::now();
+        assert!(typing.should_send_at("bob", start));
+        assert!(!typing.should_send_at("bob", start + SECOND));
+        assert!(typing.should_send_at("ops", start + SECOND));
+        assert!(!typing.should_send_at("bob", start + SEND_INTERVAL - SECOND));
+        assert!(typing.should_send_at("bob", start + SEND_INTERVAL));
+    }
+
+    #[test]
+    fn sending_a_message_resets_the_throttle() {
+        let mut typing = Typing::default();
+        let start = Instant::now();
+        assert!(typing.should_send_at("bob", start));
+        typing.message_sent();
+        assert!(typing.should_send_at("bob", start + SECOND));
+    }
+
+    #[test]
+    fn typing_users_expire() {
+        let mut typing = Typing::default();
+        let start = Instant::now();
+        typing.received_at("alice", start);
+        typing.received_at("bob", start + 2 * SECOND);
+        // a new notification does not add the user twice
+        typing.received_at("alice", start + 3 * SECOND);
+        assert_eq!(typing.names(), vec!["alice", "bob"]);
+
+        assert!(!typing.expire_at(start + EXPIRY));
+        assert!(typing.expire_at(start + 2 * SECOND + EXPIRY));
+        assert_eq!(typing.names(), vec!["alice"]);
+        assert!(typing.expire_at(start + 3 * SECOND + EXPIRY));
+        assert!(typing.names().is_empty());
+    }
+
+    #[test]
+    fn a_message_ends_the_typing() {
+        let mut typing = Typing::default();
+        typing.received("alice");
+        typing.received("bob");
+        typing.stopped("alice");
+        assert_eq!(typing.names(), vec!["bob"]);
+    }
+}
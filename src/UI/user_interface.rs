@@ -1,6 +1,7 @@
 use crate::UI::app::App;
+use crate::UI::keymap::{Action, KeyScope};
 use crate::UI::tabs::tabs::SelectedTab;
-use crate::types;
+use crate::UI::theme;
 use Constraint::{Length, Min};
 use color_eyre::owo_colors::OwoColorize;
 use ratatui::prelude::Buffer;
@@ -17,6 +18,8 @@ use ratatui::{
     style::{Color, Style, Stylize},
     widgets::{Block, BorderType, Paragraph, Scrollbar, ScrollbarOrientation, Widget},
 };
+use rust_chat_client::helper;
+use rust_chat_client::i18n::catalog::{tr_args, tr_count};
 use strum::IntoEnumIterator;
 use tui_textarea::TextArea;
 
@@ -24,6 +27,7 @@ use tui_textarea::TextArea;
 /// Rendert die gesamte UI.
 /// Diese Funktion nimmt ein &mut App, damit das TextArea Widget mutiert werden kann.
 pub fn render_ui(app: &mut App, frame: &mut Frame) {
+    app.hyperlinks.clear();
     let area = frame.area();
     let vertical = Layout::vertical([Length(1), Min(0)]);
     let [header_area, inner_area] = vertical.areas(area);
@@ -33,7 +37,7 @@ pub fn render_ui(app: &mut App, frame: &mut Frame) {
 
     {
         let buf = frame.buffer_mut();
-        render_title(title_area, buf);
+        render_title(app, title_area, buf);
         render_tabs(app, tabs_area, buf);
         // render_footer(footer_area, buf);
     }
@@ -41,10 +45,10 @@ pub fn render_ui(app: &mut App, frame: &mut Frame) {
 }
 
 fn render_tabs(app: &mut App, area: Rect, buf: &mut Buffer) {
+    app.tabs_area = area;
     let titles = SelectedTab::iter().map(SelectedTab::title);
-    let highlight_style = Style::default()
-        .fg(app.selected_tab.palette().fg)
-        .bg(app.selected_tab.palette().bg);
+    let palette = app.selected_tab.palette(&theme::current());
+    let highlight_style = Style::default().fg(palette.fg).bg(palette.bg);
     let selected_tab_index = app.selected_tab as usize;
     Tabs::new(titles)
         .highlight_style(highlight_style)
@@ -54,8 +58,34 @@ fn render_tabs(app: &mut App, area: Rect, buf: &mut Buffer) {
         .render(area, buf);
 }
 
-fn render_title(area: Rect, buf: &mut Buffer) {
-    Paragraph::new(Line::from("change tab with [Shift <] | [Shift >] ").dim())
+// returns the tab whose title is at the given column of the tab bar
+pub fn tab_at(area: Rect, column: u16) -> Option<SelectedTab> {
+    let mut x = area.x;
+    for tab in SelectedTab::iter() {
+        let width = tab.title().width() as u16;
+        if (x..x + width).contains(&column) {
+            return Some(tab);
+        }
+        // divider between the titles
+        x += width + 1;
+    }
+    None
+}
+
+fn render_title(app: &App, area: Rect, buf: &mut Buffer) {
+    let hint = app
+        .keymap
+        .hint(KeyScope::Global, &[Action::NextTab, Action::PreviousTab]);
+    let mut title = Line::from(format!("{hint} ")).fg(theme::current().dim);
+    // mentions which weren't looked at yet
+    let unread = app.mentions_table.unread;
+    if unread > 0 {
+        title.spans.insert(0, Span::from(" "));
+        title
+            .spans
+            .insert(0, mention_span(tr_count("mentions-unread", unread)));
+    }
+    Paragraph::new(title)
         .alignment(Alignment::Right)
         .render(area, buf);
 }
@@ -66,23 +96,95 @@ fn render_title(area: Rect, buf: &mut Buffer) {
 //         .render(area, buf);
 // }
 
-// Einfache Farbstile
-pub fn red_span(text: String) -> Span<'static> {
-    Span::styled(text, Style::new().fg(types::RED_COLOR))
+// Einfache Farbstile, die Farben kommen aus dem aktuellen Theme
+pub fn error_span(text: String) -> Span<'static> {
+    Span::styled(text, Style::new().fg(theme::current().error))
+}
+
+pub fn info_span(text: String) -> Span<'static> {
+    Span::styled(text, Style::new().fg(theme::current().info))
+}
+
+pub fn accent_span(text: String) -> Span<'static> {
+    Span::styled(text, Style::new().fg(theme::current().accent))
+}
+
+pub fn name_span(text: String) -> Span<'static> {
+    Span::styled(text, Style::new().fg(theme::current().name))
 }
 
-pub fn blue_span(text: String) -> Span<'static> {
-    Span::styled(text, Style::new().fg(types::BLUE_COLOR))
+pub fn success_span(text: String) -> Span<'static> {
+    Span::styled(text, Style::new().fg(theme::current().success))
 }
 
-pub fn purple_span(text: String) -> Span<'static> {
-    Span::styled(text, Style::new().fg(types::PURPLE_COLOR))
+pub fn dim_span(text: String) -> Span<'static> {
+    Span::styled(text, Style::new().fg(theme::current().dim))
 }
 
-pub fn turkis_span(text: String) -> Span<'static> {
-    Span::styled(text, Style::new().fg(types::TURKIS_COLOR))
+pub fn mention_span(text: String) -> Span<'static> {
+    Span::styled(
+        text,
+        Style::new()
+            .fg(theme::current().mention)
+            .add_modifier(Modifier::BOLD),
+    )
 }
 
-pub fn green_span(text: String) -> Span<'static> {
-    Span::styled(text, Style::new().fg(types::GREEN_COLOR))
+// the name of a user in the color of its client
+pub fn client_span(name: String, client_id: &str) -> Span<'static> {
+    Span::styled(
+        name,
+        Style::new().fg(theme::current().name_color(client_id)),
+    )
+}
+
+// the initials of a user on its color, shown in front of the name
+pub fn badge_span(name: &str, client_id: &str) -> Span<'static> {
+    let theme = theme::current();
+    Span::styled(
+        format!(" {} ", helper::initials(name)),
+        Style::new()
+            .fg(theme.background)
+            .bg(theme.name_color(client_id))
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+// the name of the sender of a message, with its badge if they are enabled
+pub fn sender_spans(name: String, client_id: &str, badge: bool) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    if badge {
+        spans.push(badge_span(&name, client_id));
+        spans.push(Span::from(" "));
+    }
+    spans.push(client_span(name, client_id));
+    spans
+}
+
+// Erzeugt eine Zeile aus einer Übersetzung, die Argumente werden als eigene Spans eingesetzt.
+// Der restliche Text wird mit `base` gestylt.
+pub fn translated_line(
+    key: &str,
+    args: Vec<(&str, Span<'static>)>,
+    base: fn(String) -> Span<'static>,
+) -> Line<'static> {
+    // every argument is replaced with a marker containing its index
+    let markers: Vec<(&str, String)> = args
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| (*name, format!("\u{1}{i}\u{1}")))
+        .collect();
+    let translated = tr_args(key, &markers);
+
+    let mut spans = Vec::new();
+    for (i, part) in translated.split('\u{1}').enumerate() {
+        if i % 2 == 0 {
+            if !part.is_empty() {
+                spans.push(base(part.to_string()));
+            }
+        } else if let Some((_, span)) = part.parse::<usize>().ok().and_then(|n| args.get(n)) {
+            spans.push(span.clone());
+        }
+    }
+    Line::from(spans)
 }
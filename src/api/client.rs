@@ -0,0 +1,281 @@
+use crate::api::event::Event;
+use crate::chat::chat_client::ChatClient;
+use crate::chat::replies::ReplyTo;
+use crate::plugins::middleware::Middleware;
+use crate::plugins::plugin_registry::{PluginConfig, PluginRegistry, PluginTrait};
+use crate::service::user_service::UserService;
+use crate::types::{ChatErrorWithMsg, Response};
+use futures::Stream;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::mpsc::Receiver;
+use tokio::task::JoinHandle;
+
+pub const DEFAULT_SERVER_URL: &str = "http://localhost:8080";
+
+/// The stream returned by [`Client::events`].
+pub type EventStream = Pin<Box<dyn Stream<Item = Event> + Send>>;
+
+/// Builds a [`Client`].
+///
+/// ```no_run
+/// # async fn run() -> Result<(), rust_chat_client::types::ChatErrorWithMsg> {
+/// use futures::StreamExt;
+/// use rust_chat_client::{Client, Event};
+///
+/// let mut client = Client::builder("http://localhost:8080").build().await;
+/// client.register("deploy-bot").await?;
+/// client.send("deploy done").await?;
+///
+/// let mut events = client.events().expect("events are taken once");
+/// while let Some(event) = events.next().await {
+///     if let Event::Message { name, content, .. } = event {
+///         println!("{name}: {content}");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ClientBuilder {
+    server_url: String,
+    channel_size: usize,
+    plugins: Vec<(&'static str, Box<dyn PluginTrait>, bool)>,
+    middleware: Vec<(String, Box<dyn Middleware>)>,
+    config: PluginConfig,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        ClientBuilder::new(DEFAULT_SERVER_URL)
+    }
+}
+
+impl ClientBuilder {
+    pub fn new(server_url: impl Into<String>) -> Self {
+        ClientBuilder {
+            server_url: server_url.into(),
+            channel_size: 1000,
+            plugins: Vec::new(),
+            middleware: Vec::new(),
+            config: PluginConfig::default(),
+        }
+    }
+
+    /// Loads the rhai scripts of the directory as commands, no scripts are loaded by default.
+    pub fn scripts_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.scripts_dir = Some(dir.into());
+        self
+    }
+
+    /// Reads the aliases from the file and saves the ones defined with `/alias` to it.
+    /// Without a file the aliases are only kept while the client runs.
+    pub fn aliases_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.aliases_file = Some(path.into());
+        self
+    }
+
+    /// Starts the middleware with the name enabled or disabled, whatever it was added with.
+    pub fn middleware_enabled(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        self.config.middleware.insert(name.into(), enabled);
+        self
+    }
+
+    /// Number of responses which are buffered until they are read, defaults to 1000.
+    pub fn channel_size(mut self, channel_size: usize) -> Self {
+        self.channel_size = channel_size.max(1);
+        self
+    }
+
+    /// Adds a plugin for a command like `/deploy`, a built-in plugin of the
+    /// same command is replaced. The plugin can only be used while registered.
+    /// If the plugin returns a [`PluginInfo`](crate::PluginInfo) from [`PluginTrait::info`], its
+    /// arguments are checked before it runs and its aliases are registered too.
+    pub fn plugin(mut self, command: &'static str, plugin: impl PluginTrait + 'static) -> Self {
+        self.plugins.push((command, Box::new(plugin), false));
+        self
+    }
+
+    /// Like [`ClientBuilder::plugin`], but the plugin can also be used without being registered.
+    ///
+    /// ```
+    /// use async_trait::async_trait;
+    /// use rust_chat_client::{ChatErrorWithMsg, Client, Message, PluginTrait};
+    ///
+    /// struct Shout;
+    ///
+    /// #[async_trait]
+    /// impl PluginTrait for Shout {
+    ///     async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+    ///         Ok(msg.content.trim().to_uppercase())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), ChatErrorWithMsg> {
+    /// let client = Client::builder("http://localhost:8080")
+    ///     .local_plugin("/shout", Shout)
+    ///     .build()
+    ///     .await;
+    /// assert!(!client.is_registered().await);
+    /// assert_eq!(client.execute("/shout hello").await?, "HELLO");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn local_plugin(
+        mut self,
+        command: &'static str,
+        plugin: impl PluginTrait + 'static,
+    ) -> Self {
+        self.plugins.push((command, Box::new(plugin), true));
+        self
+    }
+
+    /// Appends a middleware which changes the posted messages and the received
+    /// responses. Outgoing messages pass the middleware in the order they were
+    /// added, incoming responses in the reverse order.
+    pub fn middleware(
+        mut self,
+        name: impl Into<String>,
+        middleware: impl Middleware + 'static,
+    ) -> Self {
+        self.middleware.push((name.into(), Box::new(middleware)));
+        self
+    }
+
+    /// Creates the client and starts polling the server once it is registered.
+    pub async fn build(self) -> Client {
+        let (tx, receiver) = tokio::sync::mpsc::channel(self.channel_size);
+        let chat_client = Arc::new(ChatClient::new_client(self.server_url, tx).await);
+
+        let mut registry = PluginRegistry::register_plugins(chat_client.clone(), self.config);
+        for (command, plugin, local) in self.plugins {
+            registry.register(command, plugin, local);
+        }
+        for (name, middleware) in self.middleware {
+            registry.register_middleware(&name, middleware, true);
+        }
+        let user_service = Arc::new(UserService::with_registry(chat_client.clone(), registry));
+
+        let poller = tokio::spawn(async move {
+            chat_client.response_poller().await;
+        });
+
+        Client {
+            user_service,
+            receiver: Some(receiver),
+            poller,
+        }
+    }
+}
+
+/// A connection to the chat server.
+///
+/// Commands are executed by the same plugins as in the terminal UI,
+/// everything the server sends is available through [`Client::events`].
+pub struct Client {
+    user_service: Arc<UserService>,
+    receiver: Option<Receiver<Response>>,
+    poller: JoinHandle<()>,
+}
+
+impl Client {
+    pub fn builder(server_url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder::new(server_url)
+    }
+
+    /// Registers at the server, the name has to be between 3 and 50 characters long.
+    pub async fn register(&self, name: &str) -> Result<(), ChatErrorWithMsg> {
+        self.execute(&format!("/register {name}")).await.map(|_| ())
+    }
+
+    /// Unregisters from the server.
+    pub async fn quit(&self) -> Result<(), ChatErrorWithMsg> {
+        self.execute("/quit").await.map(|_| ())
+    }
+
+    /// Sends a message to everyone, or to the group if the client joined one.
+    pub async fn send(&self, text: &str) -> Result<(), ChatErrorWithMsg> {
+        self.execute(&format!("/broadcast {text}"))
+            .await
+            .map(|_| ())
+    }
+
+    /// Sends a private message to the client with the given id.
+    pub async fn send_private(&self, client_id: &str, text: &str) -> Result<(), ChatErrorWithMsg> {
+        self.execute(&format!("/private {client_id} {text}"))
+            .await
+            .map(|_| ())
+    }
+
+    /// Answers a message to everyone or the group, clients show the parent above it.
+    pub async fn reply(&self, reply_to: ReplyTo, text: &str) -> Result<(), ChatErrorWithMsg> {
+        self.user_service
+            .reply(&format!("/broadcast {text}"), reply_to)
+            .await
+            .map(|_| ())
+    }
+
+    /// Executes any input the terminal UI accepts, e.g. `/group join ops`.
+    /// Answers of the server arrive as events.
+    pub async fn execute(&self, input: &str) -> Result<String, ChatErrorWithMsg> {
+        self.user_service.execute(input).await
+    }
+
+    pub async fn is_registered(&self) -> bool {
+        *self.user_service.chat_client.registered.lock().await
+    }
+
+    pub async fn name(&self) -> String {
+        self.user_service
+            .chat_client
+            .client_name
+            .lock()
+            .await
+            .clone()
+    }
+
+    pub async fn client_id(&self) -> String {
+        self.user_service.chat_client.client_id.lock().await.clone()
+    }
+
+    /// Returns the decoded responses of the server, can only be taken once.
+    /// Joining and leaving groups and the user list are applied to the client while
+    /// the stream is read.
+    pub fn events(&mut self) -> Option<EventStream> {
+        let receiver = self.receiver.take()?;
+        let chat_client = self.user_service.chat_client.clone();
+        let stream = futures::stream::unfold(
+            (receiver, chat_client),
+            |(mut receiver, chat_client)| async move {
+                loop {
+                    let rsp = receiver.recv().await?;
+                    let Some(event) = Event::from_response(rsp) else {
+                        continue;
+                    };
+                    match &event {
+                        Event::GroupJoined(group) => {
+                            *chat_client.group.lock().await = Some(group.clone());
+                        }
+                        Event::GroupLeft => *chat_client.group.lock().await = None,
+                        Event::Users(users) => *chat_client.users.lock().await = users.clone(),
+                        _ => {}
+                    }
+                    return Some((event, (receiver, chat_client)));
+                }
+            },
+        );
+        Some(Box::pin(stream))
+    }
+
+    /// The service behind the client, for tools which need the lower level API.
+    pub fn user_service(&self) -> Arc<UserService> {
+        self.user_service.clone()
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        self.poller.abort();
+    }
+}
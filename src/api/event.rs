@@ -0,0 +1,176 @@
+use crate::chat::control;
+use crate::chat::edits::{self, ChatEdit};
+use crate::chat::outbox::OutboxUpdate;
+use crate::chat::reactions;
+use crate::chat::receipts::{self, DeliveryStatus, DeliveryUpdate};
+use crate::chat::replies::{self, ReplyTo};
+use crate::types::{
+    ADD_GROUP_FLAG, DELIVERY_FLAG, EDIT_FLAG, GROUP_HELP_FLAG, HELP_FLAG, IGNORE_RESPONSE_TAG,
+    JsonClient, JsonGroup, LEAVE_GROUP_FLAG, OUTBOX_FLAG, REACTION_FLAG, Response, TYPING_FLAG,
+    USER_ADD_FLAG, USER_REMOVE_FLAG, USERS_FLAG,
+};
+
+/// A decoded response of the server.
+///
+/// New variants may be added in minor releases, responses which are not
+/// decoded yet are passed on as [`Event::Other`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Event {
+    /// A chat message of a user, broadcast, group or private. The id is empty
+    /// for clients without message ids, `reply_to` is set for replies.
+    /// `private` is set for private messages, answers should be private as well.
+    Message {
+        client_id: String,
+        name: String,
+        content: String,
+        message_id: String,
+        reply_to: Option<ReplyTo>,
+        private: bool,
+    },
+    /// A user changed the text of one of its messages.
+    Edited {
+        client_id: String,
+        message_id: String,
+        content: String,
+    },
+    /// A user deleted one of its messages.
+    Deleted {
+        client_id: String,
+        message_id: String,
+    },
+    /// A user reacted to a message with an emoji or took the reaction back.
+    /// Own reactions to private messages have the own client id.
+    Reaction {
+        client_id: String,
+        message_id: String,
+        emoji: String,
+        added: bool,
+    },
+    /// The state of an own private message changed, `client_id` is the receiver.
+    Delivery {
+        client_id: String,
+        message_id: String,
+        status: DeliveryStatus,
+    },
+    /// A chat message was queued because the server was not reachable, or a
+    /// queued message was sent, failed or cancelled.
+    Outbox(OutboxUpdate),
+    /// A user is writing a private message to the client or a message to its group.
+    Typing { client_id: String, name: String },
+    /// An info text of the server or the result of a command.
+    Info(String),
+    /// An error of the server or of a command.
+    Error(String),
+    /// The name of a user who joined the chat.
+    UserJoined(String),
+    /// The name of a user who left the chat.
+    UserLeft(String),
+    /// The client joined a group, following messages are sent to it.
+    GroupJoined(JsonGroup),
+    /// The client left its group.
+    GroupLeft,
+    /// The answer to `/users` or `/group users`.
+    Users(Vec<JsonClient>),
+    /// Every other response, e.g. the help tables.
+    Other(Response),
+}
+
+impl Event {
+    /// Decodes a response, returns `None` for responses without content.
+    pub fn from_response(rsp: Response) -> Option<Event> {
+        if rsp.err == IGNORE_RESPONSE_TAG {
+            return None;
+        }
+        if !rsp.err.is_empty() {
+            return Some(Event::Error(rsp.err));
+        }
+        if rsp.content.is_empty() && rsp.rsp_name != LEAVE_GROUP_FLAG {
+            return None;
+        }
+
+        let event = match rsp.rsp_name.as_str() {
+            "" => Event::Info(rsp.content),
+            USER_ADD_FLAG => Event::UserJoined(rsp.content),
+            USER_REMOVE_FLAG => Event::UserLeft(rsp.content),
+            LEAVE_GROUP_FLAG => Event::GroupLeft,
+            ADD_GROUP_FLAG => match serde_json::from_str(&rsp.content) {
+                Ok(group) => Event::GroupJoined(group),
+                Err(e) => Event::Error(e.to_string()),
+            },
+            USERS_FLAG => match serde_json::from_str(&rsp.content) {
+                Ok(users) => Event::Users(users),
+                Err(e) => Event::Error(e.to_string()),
+            },
+            HELP_FLAG | GROUP_HELP_FLAG => Event::Other(rsp),
+            DELIVERY_FLAG => match serde_json::from_str::<DeliveryUpdate>(&rsp.content) {
+                Ok(update) => Event::Delivery {
+                    client_id: rsp.client_id,
+                    message_id: update.id,
+                    status: update.status,
+                },
+                Err(e) => Event::Error(e.to_string()),
+            },
+            // own edits are only shown by the UI
+            EDIT_FLAG => Event::Other(rsp),
+            OUTBOX_FLAG => match serde_json::from_str(&rsp.content) {
+                Ok(update) => Event::Outbox(update),
+                Err(e) => Event::Error(e.to_string()),
+            },
+            _ if let Some((status, id)) = receipts::parse_receipt(&rsp.content) => {
+                Event::Delivery {
+                    message_id: id.to_string(),
+                    client_id: rsp.client_id,
+                    status,
+                }
+            }
+            _ if (!rsp.client_id.is_empty() || rsp.rsp_name == REACTION_FLAG)
+                && let Some(reaction) = reactions::parse(&rsp.content) =>
+            {
+                Event::Reaction {
+                    message_id: reaction.id.to_string(),
+                    emoji: reaction.emoji.to_string(),
+                    added: reaction.added,
+                    client_id: rsp.client_id,
+                }
+            }
+            _ if !rsp.client_id.is_empty()
+                && let Some(edit) = edits::parse(&rsp.content) =>
+            {
+                match edit {
+                    ChatEdit::Edit { id, text } => Event::Edited {
+                        message_id: id.to_string(),
+                        content: text.to_string(),
+                        client_id: rsp.client_id,
+                    },
+                    ChatEdit::Delete { id } => Event::Deleted {
+                        message_id: id.to_string(),
+                        client_id: rsp.client_id,
+                    },
+                }
+            }
+            _ if !rsp.client_id.is_empty() && rsp.content == TYPING_FLAG => Event::Typing {
+                client_id: rsp.client_id,
+                name: rsp.rsp_name,
+            },
+            // control messages of newer clients are no chat messages
+            _ if control::is_control(&rsp.content) => Event::Other(rsp),
+            // the id of private messages is not part of the text
+            _ if !rsp.client_id.is_empty() => {
+                let private = receipts::wants_receipt(&rsp.content);
+                let (content, id) = receipts::split_id(&rsp.content);
+                let (reply_to, content) = replies::split_reply(content);
+                Event::Message {
+                    private,
+                    content: content.to_string(),
+                    message_id: id.unwrap_or_default().to_string(),
+                    reply_to,
+                    client_id: rsp.client_id,
+                    name: rsp.rsp_name,
+                }
+            }
+            _ => Event::Other(rsp),
+        };
+        Some(event)
+    }
+}
@@ -0,0 +1,187 @@
+use crate::api::client::EventStream;
+use crate::api::event::Event;
+use crate::chat::chat_client::ChatClient;
+use crate::chat::mentions;
+use crate::types::ChatErrorWithMsg;
+use async_trait::async_trait;
+use core::fmt::Debug;
+use futures::StreamExt;
+use std::sync::Arc;
+
+// Trigger decides which incoming messages are handed to a bot
+#[derive(Debug, Clone, PartialEq)]
+pub enum Trigger {
+    // the message starts with the command e.g. "!dice", the rest are the args
+    Command(&'static str),
+    // the message contains "@<own name>", the args are the message without the mention
+    Mention,
+    // the message contains the text, case is ignored
+    Contains(String),
+    // every message
+    Any,
+}
+
+// IncomingMessage is a chat message of another user
+#[derive(Debug, Clone)]
+pub struct IncomingMessage {
+    pub client_id: String,
+    pub name: String,
+    pub content: String,
+    // content without the command or mention
+    pub args: String,
+    // the message was sent only to the bot
+    pub private: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    // in the conversation of the message, privately to private messages
+    Text(String),
+    // to everyone, or to the group if the bot joined one, also for private messages
+    Public(String),
+    // only to the sender of the message
+    Private(String),
+}
+
+// BotHandler is the inbound counterpart of PluginTrait,
+// it handles messages of other users instead of commands of the own user
+#[async_trait]
+pub trait BotHandler: Send + Sync {
+    fn name(&self) -> &str;
+    fn trigger(&self) -> Trigger;
+    async fn handle(
+        &self,
+        ctx: &BotContext,
+        msg: IncomingMessage,
+    ) -> Result<Option<Reply>, ChatErrorWithMsg>;
+}
+
+impl Debug for dyn BotHandler {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "BotHandler {}", self.name())
+    }
+}
+
+// BotContext lets bots send replies, also later on e.g. for reminders
+#[derive(Debug, Clone)]
+pub struct BotContext {
+    chat_client: Arc<ChatClient>,
+}
+
+impl BotContext {
+    pub fn new(chat_client: Arc<ChatClient>) -> Self {
+        BotContext { chat_client }
+    }
+
+    // replies get an id like every chat message and are queued while the server is not reachable
+    pub async fn reply(&self, to: &IncomingMessage, reply: Reply) -> Result<(), ChatErrorWithMsg> {
+        let (plugin, text, receiver) = match reply {
+            Reply::Text(text) if !to.private => ("/broadcast", text, ""),
+            Reply::Public(text) => ("/broadcast", text, ""),
+            Reply::Text(text) | Reply::Private(text) => ("/private", text, to.client_id.as_str()),
+        };
+        self.chat_client
+            .send_text(plugin, &text, receiver, None)
+            .await
+    }
+}
+
+// BotRegistry hands incoming messages to the bots whose trigger matches
+#[derive(Debug)]
+pub struct BotRegistry {
+    bots: Vec<Box<dyn BotHandler>>,
+    ctx: BotContext,
+}
+
+impl BotRegistry {
+    pub fn new(chat_client: Arc<ChatClient>) -> BotRegistry {
+        BotRegistry {
+            bots: Vec::new(),
+            ctx: BotContext::new(chat_client),
+        }
+    }
+
+    pub fn add(&mut self, bot: Box<dyn BotHandler>) {
+        self.bots.push(bot);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bots.is_empty()
+    }
+
+    // Run dispatches the messages of the stream until it ends
+    pub async fn run(&self, mut events: EventStream) {
+        while let Some(event) = events.next().await {
+            if let Event::Message {
+                client_id,
+                name,
+                content,
+                private,
+                ..
+            } = event
+            {
+                self.dispatch(client_id, name, content, private).await;
+            }
+        }
+    }
+
+    pub async fn dispatch(&self, client_id: String, name: String, content: String, private: bool) {
+        // own messages are sent back by the server, bots must not answer themselves
+        if client_id.is_empty() || client_id == *self.ctx.chat_client.client_id.lock().await {
+            return;
+        }
+        let own_name = self.ctx.chat_client.client_name.lock().await.clone();
+
+        for bot in &self.bots {
+            let Some(args) = matches(&bot.trigger(), &content, &own_name) else {
+                continue;
+            };
+            let msg = IncomingMessage {
+                client_id: client_id.clone(),
+                name: name.clone(),
+                content: content.clone(),
+                args,
+                private,
+            };
+            // errors are only shown to the sender
+            let reply = match bot.handle(&self.ctx, msg.clone()).await {
+                Ok(reply) => reply,
+                Err(e) => Some(Reply::Private(format!("{}: {}", e.kind, e.msg))),
+            };
+            if let Some(reply) = reply {
+                let _ = self.ctx.reply(&msg, reply).await;
+            }
+        }
+    }
+}
+
+// returns the args if the trigger matches the content
+fn matches(trigger: &Trigger, content: &str, own_name: &str) -> Option<String> {
+    let content = content.trim();
+    match trigger {
+        Trigger::Command(command) => {
+            let rest = content.strip_prefix(command)?;
+            if !rest.is_empty() && !rest.starts_with(' ') {
+                return None;
+            }
+            Some(rest.trim().to_string())
+        }
+        // "@bobby" is no mention of bob
+        Trigger::Mention => {
+            let found = mentions::mentions_of(content, own_name);
+            if own_name.is_empty() || found.is_empty() {
+                return None;
+            }
+            let mut args = content.to_string();
+            for mention in found.iter().rev() {
+                args.replace_range(mention.range.clone(), "");
+            }
+            Some(args.trim().to_string())
+        }
+        Trigger::Contains(text) => content
+            .to_lowercase()
+            .contains(&text.to_lowercase())
+            .then(|| content.to_string()),
+        Trigger::Any => Some(content.to_string()),
+    }
+}
@@ -0,0 +1,176 @@
+use crate::bots::bot_registry::{BotContext, BotHandler, IncomingMessage, Reply, Trigger};
+use crate::i18n::catalog::{tr, tr_args};
+use crate::types::{ChatError, ChatErrorWithMsg};
+use async_trait::async_trait;
+use rand::Rng;
+use std::time::Duration;
+
+pub const BOTS: [&str; 3] = ["echo", "dice", "reminder"];
+
+// returns one of the example bots by name
+pub fn bot_by_name(name: &str) -> Option<Box<dyn BotHandler>> {
+    match name {
+        "echo" => Some(Box::new(EchoBot)),
+        "dice" => Some(Box::new(DiceBot)),
+        "reminder" => Some(Box::new(ReminderBot)),
+        _ => None,
+    }
+}
+
+// EchoBot answers "!echo <text>" with the text
+pub struct EchoBot;
+
+#[async_trait]
+impl BotHandler for EchoBot {
+    fn name(&self) -> &str {
+        "echo"
+    }
+
+    fn trigger(&self) -> Trigger {
+        Trigger::Command("!echo")
+    }
+
+    async fn handle(
+        &self,
+        _ctx: &BotContext,
+        msg: IncomingMessage,
+    ) -> Result<Option<Reply>, ChatErrorWithMsg> {
+        if msg.args.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Reply::Text(msg.args)))
+    }
+}
+
+// DiceBot answers "!dice" or "!dice 2d20" with the rolled numbers
+pub struct DiceBot;
+
+const MAX_DICE: u32 = 20;
+const MAX_SIDES: u32 = 1000;
+
+#[async_trait]
+impl BotHandler for DiceBot {
+    fn name(&self) -> &str {
+        "dice"
+    }
+
+    fn trigger(&self) -> Trigger {
+        Trigger::Command("!dice")
+    }
+
+    async fn handle(
+        &self,
+        _ctx: &BotContext,
+        msg: IncomingMessage,
+    ) -> Result<Option<Reply>, ChatErrorWithMsg> {
+        let (count, sides) = parse_dice(&msg.args)?;
+        let rolls: Vec<u32> = {
+            let mut rng = rand::rng();
+            (0..count).map(|_| rng.random_range(1..=sides)).collect()
+        };
+        let total: u32 = rolls.iter().sum();
+        let rolls = rolls
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Ok(Some(Reply::Text(tr_args(
+            "bot-dice",
+            &[
+                ("name", msg.name),
+                ("dice", format!("{count}d{sides}")),
+                ("rolls", rolls),
+                ("total", total.to_string()),
+            ],
+        ))))
+    }
+}
+
+// parses "", "d20", "3" or "2d20" into the number of dice and their sides
+fn parse_dice(args: &str) -> Result<(u32, u32), ChatErrorWithMsg> {
+    let wrong_input = || {
+        ChatErrorWithMsg::new(
+            ChatError::WrongInput,
+            tr_args("bot-dice-usage", &[("max", MAX_DICE.to_string())]),
+        )
+    };
+    let args = args.trim().to_lowercase();
+    if args.is_empty() {
+        return Ok((1, 6));
+    }
+
+    let (count, sides) = match args.split_once('d') {
+        Some((count, sides)) => (count, sides),
+        None => (args.as_str(), "6"),
+    };
+    let count = if count.is_empty() {
+        1
+    } else {
+        count.parse().map_err(|_| wrong_input())?
+    };
+    let sides = sides.parse().map_err(|_| wrong_input())?;
+
+    if !(1..=MAX_DICE).contains(&count) || !(2..=MAX_SIDES).contains(&sides) {
+        return Err(wrong_input());
+    }
+    Ok((count, sides))
+}
+
+// ReminderBot answers "!remind 10m <text>" after the given time with the text
+pub struct ReminderBot;
+
+#[async_trait]
+impl BotHandler for ReminderBot {
+    fn name(&self) -> &str {
+        "reminder"
+    }
+
+    fn trigger(&self) -> Trigger {
+        Trigger::Command("!remind")
+    }
+
+    async fn handle(
+        &self,
+        ctx: &BotContext,
+        msg: IncomingMessage,
+    ) -> Result<Option<Reply>, ChatErrorWithMsg> {
+        let wrong_input = || ChatErrorWithMsg::new(ChatError::WrongInput, tr("bot-remind-usage"));
+        let (time, text) = msg.args.split_once(' ').ok_or_else(wrong_input)?;
+        let delay = parse_duration(time).ok_or_else(wrong_input)?;
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            return Err(wrong_input());
+        }
+
+        let ctx = ctx.clone();
+        let reminder = tr_args("bot-remind", &[("text", text)]);
+        let msg_clone = msg.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let _ = ctx.reply(&msg_clone, Reply::Private(reminder)).await;
+        });
+
+        Ok(Some(Reply::Private(tr_args(
+            "bot-remind-set",
+            &[("time", time.to_string())],
+        ))))
+    }
+}
+
+// parses durations like "30s", "10m" or "2h", plain numbers are seconds
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim().to_lowercase();
+    let (number, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s.as_str(), "s"),
+    };
+    let number: u64 = number.parse().ok()?;
+    let factor = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        _ => return None,
+    };
+    number.checked_mul(factor).map(Duration::from_secs)
+}
@@ -0,0 +1,2 @@
+pub mod bot_registry;
+pub mod example_bots;
@@ -1,6 +1,9 @@
+use crate::chat::edits::SentMessages;
+use crate::chat::outbox::Outbox;
+use crate::chat::reactions::OwnReactions;
 use crate::helper;
 use crate::network::http_client::HttpClient;
-use crate::types::{self, ChatErrorWithMsg, JsonGroup};
+use crate::types::{self, ChatErrorWithMsg, JsonClient, JsonGroup};
 use crate::types::{Message, Response};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -15,8 +18,16 @@ pub struct ChatClient {
     pub auth_token: Arc<Mutex<String>>,
     pub group: Arc<Mutex<Option<JsonGroup>>>,
     pub registered: Arc<Mutex<bool>>,
+    // user list of the last /users answer
+    pub users: Arc<Mutex<Vec<JsonClient>>>,
     pub output: Sender<Response>,
     pub http_client: HttpClient,
+    // chat messages which could not be sent yet
+    pub outbox: Outbox,
+    // own messages which can be edited or deleted
+    pub sent_messages: SentMessages,
+    // own reactions, a second reaction with the same emoji takes it back
+    pub own_reactions: OwnReactions,
     // pub current_calling: String,
     notify: Notify,
     // LogChan                chan t.Log
@@ -38,8 +49,12 @@ impl ChatClient {
             group: Arc::new(Mutex::new(None)),
             auth_token: auth_token.clone(),
             registered: Arc::new(Mutex::new(false)),
+            users: Arc::new(Mutex::new(Vec::new())),
             output: tx,
             notify: Notify::new(),
+            outbox: Outbox::new(),
+            sent_messages: SentMessages::new(),
+            own_reactions: OwnReactions::new(),
             http_client: HttpClient::new_client(server_url, auth_token, client_id).await,
         }
     }
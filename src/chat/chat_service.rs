@@ -1,14 +1,25 @@
+use crate::chat::edits::SentMessage;
+use crate::chat::outbox::{OutboxState, OutboxUpdate, QueuedMessage};
+use crate::chat::replies::ReplyTo;
 use crate::helper;
+use crate::i18n::catalog::tr;
 use crate::network::http_client::HttpClient;
-use crate::types::{self, JsonClient};
-use crate::types::{Endpoint, Message, Response};
+use crate::types::{self, ChatError, ChatErrorWithMsg, JsonClient, OUTBOX_FLAG};
+use crate::types::{Endpoint, IGNORE_RESPONSE_TAG, Message, Response};
 use crate::{chat::chat_client::ChatClient, network::http_client};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::mpsc::{Receiver, channel};
 use tokio::sync::{Mutex, Notify};
 
+// waiting time between two attempts to send a queued message
+const OUTBOX_RETRY_MIN: Duration = Duration::from_secs(1);
+const OUTBOX_RETRY_MAX: Duration = Duration::from_secs(30);
+// length of the generated ids of chat messages
+pub const MESSAGE_ID_LEN: usize = 8;
+
 impl ChatClient {
     pub async fn create_message(
         &self,
@@ -42,17 +53,32 @@ impl ChatClient {
             plugin,
             client_id: msg_client_id,
             group_id: client_group_id,
+            message_id: String::new(),
+            reply_to: None,
         }
     }
 
+    // Response_poller polls the server and sends the queued messages
     pub async fn response_poller(&self) {
+        tokio::join!(self.poll_responses(), self.outbox_sender());
+    }
+
+    async fn poll_responses(&self) {
         loop {
             self.check_registered().await;
 
             match self.http_client.get_response(Endpoint::Get).await {
-                Ok(rsp) => {
+                // empty polls are no message for the middleware
+                Ok(rsp) if rsp.err == IGNORE_RESPONSE_TAG => {
+                    self.server_reachable();
                     let _ = self.output.send(rsp).await;
                 }
+                Ok(rsp) => {
+                    self.server_reachable();
+                    if let Some(rsp) = self.http_client.middleware.incoming(rsp).await {
+                        let _ = self.output.send(rsp).await;
+                    }
+                }
                 Err(_) => {
                     // TODO log channel
                     // let _ = self.output.send(Response::error(err.to_string())).await;
@@ -61,6 +87,159 @@ impl ChatClient {
         }
     }
 
+    // the queued messages do not have to wait for the next retry
+    fn server_reachable(&self) {
+        if self.outbox.has_pending() {
+            self.outbox.changed.notify_one();
+        }
+    }
+
+    // Send_chat posts a chat message. If the server is not reachable or older
+    // messages are still queued, the message is queued and sent later.
+    pub async fn send_chat(&self, msg: QueuedMessage) -> Result<(), ChatErrorWithMsg> {
+        if !self.outbox.has_pending() {
+            let _sending = self.outbox.sending.lock().await;
+            if !self.outbox.has_pending() {
+                let message = self.queued_to_message(&msg).await;
+                match self
+                    .http_client
+                    .post_message(Endpoint::PostPlugin, message)
+                    .await
+                {
+                    Err(e) if matches!(e.kind, ChatError::Offline) && self.outbox.is_enabled() => {}
+                    result => return result.map(|_| ()),
+                }
+            }
+        }
+        let queued = self.outbox.push(msg);
+        self.outbox_update(OutboxState::Queued, queued).await;
+        Ok(())
+    }
+
+    // Send_text sends a new chat message with an id, so it can be edited, reacted to
+    // and answered. The receiver is empty for broadcasts.
+    pub async fn send_text(
+        &self,
+        plugin: &str,
+        text: &str,
+        receiver: &str,
+        reply_to: Option<ReplyTo>,
+    ) -> Result<(), ChatErrorWithMsg> {
+        let id = helper::generate_secure_token(MESSAGE_ID_LEN);
+        self.send_chat(QueuedMessage {
+            id: 0,
+            plugin: plugin.to_string(),
+            text: text.to_string(),
+            receiver: receiver.to_string(),
+            message_id: id.clone(),
+            reply_to,
+            error: None,
+        })
+        .await?;
+        self.sent_messages.record(SentMessage {
+            id,
+            plugin: plugin.to_string(),
+            receiver: receiver.to_string(),
+            text: text.to_string(),
+        });
+        Ok(())
+    }
+
+    // Send_control posts a control message to the receiver or, without one, to the
+    // group. Control messages are only of use right away, they are not queued.
+    pub async fn send_control(
+        &self,
+        content: &str,
+        receiver: &str,
+    ) -> Result<(), ChatErrorWithMsg> {
+        let plugin = if receiver.is_empty() {
+            "/broadcast"
+        } else {
+            "/private"
+        };
+        let message = self
+            .create_message(
+                String::new(),
+                plugin.to_string(),
+                content.to_string(),
+                receiver.to_string(),
+            )
+            .await;
+        self.http_client
+            .post_message(Endpoint::PostPlugin, message)
+            .await
+            .map(|_| ())
+    }
+
+    // Outbox_sender sends the queued messages in their order. While the server
+    // is not reachable the waiting time between the attempts grows.
+    async fn outbox_sender(&self) {
+        let mut retry = OUTBOX_RETRY_MIN;
+        loop {
+            self.check_registered().await;
+            let Some(queued) = self.outbox.next_pending() else {
+                self.outbox.changed.notified().await;
+                continue;
+            };
+
+            let result = {
+                let _sending = self.outbox.sending.lock().await;
+                let message = self.queued_to_message(&queued).await;
+                self.http_client
+                    .post_message(Endpoint::PostPlugin, message)
+                    .await
+            };
+            match result {
+                Ok(_) => {
+                    retry = OUTBOX_RETRY_MIN;
+                    // a cancelled message could have been sent at the same time
+                    if let Some(sent) = self.outbox.remove(queued.id) {
+                        self.outbox_update(OutboxState::Sent, sent).await;
+                    }
+                }
+                Err(e) if matches!(e.kind, ChatError::Offline) => {
+                    let _ = tokio::time::timeout(retry, self.outbox.changed.notified()).await;
+                    retry = (retry * 2).min(OUTBOX_RETRY_MAX);
+                }
+                Err(e) => {
+                    if let Some(failed) = self.outbox.set_failed(queued.id, e.msg) {
+                        self.outbox_update(OutboxState::Failed, failed).await;
+                    }
+                }
+            }
+        }
+    }
+
+    // the sender is filled in when the message is sent, the receiver of a
+    // broadcast is the client itself
+    async fn queued_to_message(&self, queued: &QueuedMessage) -> Message {
+        let mut message = self
+            .create_message(
+                String::new(),
+                queued.plugin.clone(),
+                queued.text.clone(),
+                queued.receiver.clone(),
+            )
+            .await;
+        message.message_id = queued.message_id.clone();
+        message.reply_to = queued.reply_to.clone();
+        message
+    }
+
+    // tells the UI about the state of a queued message
+    pub async fn outbox_update(&self, state: OutboxState, message: QueuedMessage) {
+        let update = OutboxUpdate { state, message };
+        let _ = self
+            .output
+            .send(Response {
+                client_id: update.message.receiver.clone(),
+                rsp_name: OUTBOX_FLAG.to_string(),
+                content: serde_json::to_string(&update).unwrap_or_default(),
+                err: String::new(),
+            })
+            .await;
+    }
+
     pub async fn parse_input_to_message(&self, input: &str) -> Message {
         let new_input = input.trim_end_matches("\n");
 
@@ -86,7 +265,7 @@ impl ChatClient {
         let group_guard = self.group.lock().await;
         let group_name = match &*group_guard {
             Some(g) => g.name.clone(),
-            None => String::from("no group"),
+            None => tr("no-group"),
         };
         let group_id = match &*group_guard {
             Some(g) => g.group_id.clone(),
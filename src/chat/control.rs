@@ -0,0 +1,37 @@
+// Control messages like receipts, typing notifications, edits and reactions are
+// sent through the server like chat messages. They start with a marker which
+// user text can't produce: chat texts starting with the separator get another
+// one in front when they are sent, which the receivers remove again.
+
+// U+2063 INVISIBLE SEPARATOR
+const ESCAPE: char = '\u{2063}';
+const CONTROL_MARKER: &str = "\u{2063}!";
+
+pub fn control(content: &str) -> String {
+    format!("{CONTROL_MARKER}{content}")
+}
+
+pub fn is_control(content: &str) -> bool {
+    content.starts_with(CONTROL_MARKER)
+}
+
+// the content of a control message without the marker
+pub fn strip(content: &str) -> Option<&str> {
+    content.strip_prefix(CONTROL_MARKER)
+}
+
+// escapes the text of a chat message, so it is never read as a control message
+pub fn escape(text: &str) -> String {
+    if text.starts_with(ESCAPE) {
+        format!("{ESCAPE}{text}")
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn unescape(text: &str) -> &str {
+    match text.strip_prefix(ESCAPE) {
+        Some(rest) if rest.starts_with(ESCAPE) => rest,
+        _ => text,
+    }
+}
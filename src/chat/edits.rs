@@ -0,0 +1,86 @@
+use crate::chat::control;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+// Edits and deletions of sent messages. Like receipts they are control messages
+// to the receivers of the original message: "[edit:{id}] new text" and "[delete:{id}]".
+// Clients only apply them to messages of the same sender.
+
+const EDIT_PREFIX: &str = "[edit:";
+const DELETE_PREFIX: &str = "[delete:";
+// number of own messages which can still be edited
+const SENT_CAPACITY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatEdit<'a> {
+    Edit { id: &'a str, text: &'a str },
+    Delete { id: &'a str },
+}
+
+pub fn edit(id: &str, text: &str) -> String {
+    control::control(&format!("{EDIT_PREFIX}{id}] {text}"))
+}
+
+pub fn delete(id: &str) -> String {
+    control::control(&format!("{DELETE_PREFIX}{id}]"))
+}
+
+pub fn parse(content: &str) -> Option<ChatEdit<'_>> {
+    let content = control::strip(content)?;
+    if let Some(rest) = content.strip_prefix(EDIT_PREFIX) {
+        let (id, text) = rest.split_once(']')?;
+        return Some(ChatEdit::Edit {
+            id,
+            text: text.trim_start(),
+        });
+    }
+    let id = content.strip_prefix(DELETE_PREFIX)?.strip_suffix(']')?;
+    Some(ChatEdit::Delete { id })
+}
+
+// SentMessage is an own chat message, the receiver is empty for group messages
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentMessage {
+    pub id: String,
+    pub plugin: String,
+    pub receiver: String,
+    pub text: String,
+}
+
+// SentMessages keeps the last own messages, so they can be edited or deleted
+#[derive(Debug, Default)]
+pub struct SentMessages {
+    messages: Mutex<VecDeque<SentMessage>>,
+}
+
+impl SentMessages {
+    pub fn new() -> SentMessages {
+        SentMessages::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<SentMessage>> {
+        self.messages.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn record(&self, msg: SentMessage) {
+        let mut messages = self.lock();
+        if messages.len() == SENT_CAPACITY {
+            messages.pop_front();
+        }
+        messages.push_back(msg);
+    }
+
+    pub fn last(&self) -> Option<SentMessage> {
+        self.lock().back().cloned()
+    }
+
+    pub fn set_text(&self, id: &str, text: &str) {
+        if let Some(msg) = self.lock().iter_mut().find(|m| m.id == id) {
+            msg.text = text.to_string();
+        }
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.lock().retain(|m| m.id != id);
+    }
+}
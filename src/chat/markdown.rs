@@ -0,0 +1,160 @@
+// Markdown-style formatting of chat messages: **bold**, *italic* or _italic_, `code`,
+// ~~strikethrough~~ and fenced code blocks. Links are found in the plain text.
+// Markers which are not closed on the same line stay part of the text.
+
+const FENCE: &str = "```";
+const SCHEMES: [&str; 2] = ["https://", "http://"];
+// chars at the end of a link which belong to the sentence
+const LINK_TRAILER: [char; 9] = ['.', ',', ';', ':', '!', '?', ')', '\'', '"'];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Format {
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+    pub strike: bool,
+    pub link: bool,
+}
+
+// Inline is a part of a line with the same format, the text of links is the url
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inline {
+    pub text: String,
+    pub format: Format,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    // one line of text
+    Text(Vec<Inline>),
+    // the language is the word after the opening fence, it may be empty
+    Code {
+        language: String,
+        lines: Vec<String>,
+    },
+}
+
+pub fn parse(text: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let Some(language) = line.trim_start().strip_prefix(FENCE) else {
+            blocks.push(Block::Text(parse_inline(line)));
+            continue;
+        };
+        // a block which isn't closed ends with the message
+        let code = lines
+            .by_ref()
+            .take_while(|line| !line.trim_start().starts_with(FENCE))
+            .map(String::from)
+            .collect();
+        blocks.push(Block::Code {
+            language: language.trim().to_lowercase(),
+            lines: code,
+        });
+    }
+    blocks
+}
+
+// the length of a link at the start of the text
+fn link_len(text: &str) -> Option<usize> {
+    let scheme = SCHEMES.iter().find(|scheme| text.starts_with(*scheme))?;
+    let len = text.find(char::is_whitespace).unwrap_or(text.len());
+    let link = text[..len].trim_end_matches(LINK_TRAILER);
+    (link.len() > scheme.len()).then_some(link.len())
+}
+
+#[derive(Default)]
+struct InlineParser {
+    inlines: Vec<Inline>,
+    text: String,
+    format: Format,
+}
+
+impl InlineParser {
+    fn flush(&mut self) {
+        if !self.text.is_empty() {
+            self.inlines.push(Inline {
+                text: std::mem::take(&mut self.text),
+                format: self.format,
+            });
+        }
+    }
+
+    fn push(&mut self, text: &str, format: Format) {
+        self.flush();
+        self.inlines.push(Inline {
+            text: text.to_string(),
+            format,
+        });
+    }
+
+    fn toggle(&mut self, change: impl Fn(&mut Format)) {
+        self.flush();
+        change(&mut self.format);
+    }
+}
+
+pub fn parse_inline(line: &str) -> Vec<Inline> {
+    let mut parser = InlineParser::default();
+    let mut i = 0;
+    let mut previous: Option<char> = None;
+    while let Some(c) = line[i..].chars().next() {
+        let rest = &line[i..];
+        let word_start = !previous.is_some_and(char::is_alphanumeric);
+        let next = |marker: &str| rest[marker.len()..].chars().next();
+        let opens = |marker: &str| {
+            next(marker).is_some_and(|c| !c.is_whitespace())
+                && rest[marker.len()..].contains(marker)
+        };
+        let closes = !previous.is_some_and(char::is_whitespace);
+
+        let len = if c == '\\' && rest[1..].starts_with(['*', '_', '`', '~', '\\']) {
+            // escaped marker
+            parser.text.push_str(&rest[1..2]);
+            2
+        } else if word_start && let Some(len) = link_len(rest) {
+            let format = Format {
+                link: true,
+                ..parser.format
+            };
+            parser.push(&rest[..len], format);
+            len
+        } else if c == '`'
+            && let Some(end) = rest[1..].find('`').filter(|end| *end > 0)
+        {
+            let format = Format {
+                code: true,
+                ..parser.format
+            };
+            parser.push(&rest[1..end + 1], format);
+            end + 2
+        } else if rest.starts_with("**") && (parser.format.bold && closes || opens("**")) {
+            parser.toggle(|f| f.bold = !f.bold);
+            2
+        } else if rest.starts_with("~~") && (parser.format.strike && closes || opens("~~")) {
+            parser.toggle(|f| f.strike = !f.strike);
+            2
+        } else if rest.starts_with("**") || rest.starts_with("~~") {
+            parser.text.push_str(&rest[..2]);
+            2
+        } else if c == '*' && (parser.format.italic && closes || opens("*")) {
+            parser.toggle(|f| f.italic = !f.italic);
+            1
+        } else if c == '_'
+            // snake_case words are no italics
+            && (parser.format.italic && closes && !next("_").is_some_and(char::is_alphanumeric)
+                || word_start && opens("_"))
+        {
+            parser.toggle(|f| f.italic = !f.italic);
+            1
+        } else {
+            parser.text.push(c);
+            c.len_utf8()
+        };
+        previous = line[..i + len].chars().next_back();
+        i += len;
+    }
+    parser.flush();
+    parser.inlines
+}
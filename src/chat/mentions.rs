@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+// Mentions address a user in a chat message with "@name". A mention starts at
+// an @ which doesn't follow a name char, so mail addresses are no mentions.
+
+// Mention is a "@name" in a text, the range includes the @
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention<'a> {
+    pub name: &'a str,
+    pub range: Range<usize>,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+pub fn parse(text: &str) -> Vec<Mention<'_>> {
+    let mut mentions = Vec::new();
+    let mut previous = None;
+    for (start, c) in text.char_indices() {
+        if c == '@' && !previous.is_some_and(is_name_char) {
+            let rest = &text[start + 1..];
+            let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+            // a dot or dash at the end belongs to the sentence
+            let name = rest[..len].trim_end_matches(['.', '-']);
+            if !name.is_empty() {
+                mentions.push(Mention {
+                    name,
+                    range: start..start + 1 + name.len(),
+                });
+            }
+        }
+        previous = Some(c);
+    }
+    mentions
+}
+
+// the mentions of the given name in the text, the case is ignored
+pub fn mentions_of<'a>(text: &'a str, name: &str) -> Vec<Mention<'a>> {
+    let name = name.to_lowercase();
+    parse(text)
+        .into_iter()
+        .filter(|m| m.name.to_lowercase() == name)
+        .collect()
+}
+
+pub fn mentions(text: &str, name: &str) -> bool {
+    !mentions_of(text, name).is_empty()
+}
+
+// the names which start with the partial name, sorted and without duplicates
+pub fn complete<'a>(partial: &str, names: &[&'a str]) -> Vec<&'a str> {
+    let partial = partial.to_lowercase();
+    let mut matches: Vec<&str> = names
+        .iter()
+        .copied()
+        .filter(|name| !name.is_empty() && name.to_lowercase().starts_with(&partial))
+        .collect();
+    matches.sort_unstable_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()).then(a.cmp(b)));
+    matches.dedup();
+    matches
+}
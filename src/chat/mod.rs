@@ -1,2 +1,11 @@
 pub mod chat_client;
 pub mod chat_service;
+pub mod control;
+pub mod edits;
+pub mod markdown;
+pub mod mentions;
+pub mod notifications;
+pub mod outbox;
+pub mod reactions;
+pub mod receipts;
+pub mod replies;
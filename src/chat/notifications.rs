@@ -0,0 +1,95 @@
+use serde::Deserialize;
+use std::process::Stdio;
+use strum_macros::{Display, EnumString};
+use tokio::process::{Child, Command};
+
+// Notifications tell the user about events while the terminal is in the background.
+// Desktop notifications are escape sequences which the terminal turns into a
+// notification of the system, terminals without support ignore them.
+
+// the events which notify the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NotificationKind {
+    Private,
+    Mention,
+    Call,
+}
+
+// escape sequence of the desktop notification, OSC 9 (iTerm2, Windows Terminal, kitty)
+// or OSC 777 (urxvt, foot, VTE based terminals)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DesktopNotification {
+    #[default]
+    Off,
+    Osc9,
+    Osc777,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    // sender of the message or the caller
+    pub name: String,
+    pub text: String,
+    // the group, everyone or private
+    pub place: String,
+}
+
+// control chars would end the escape sequence early
+fn clean(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+// the bell and the desktop notification, written to the terminal as they are
+pub fn escape_sequences(
+    bell: bool,
+    desktop: DesktopNotification,
+    notification: &Notification,
+) -> String {
+    let mut sequences = String::new();
+    if bell {
+        sequences.push('\x07');
+    }
+    let name = clean(&notification.name);
+    let text = clean(&notification.text);
+    match desktop {
+        DesktopNotification::Off => {}
+        DesktopNotification::Osc9 => sequences.push_str(&format!("\x1b]9;{name}: {text}\x07")),
+        // the title must not contain the separator
+        DesktopNotification::Osc777 => sequences.push_str(&format!(
+            "\x1b]777;notify;{};{text}\x07",
+            name.replace(';', ",")
+        )),
+    }
+    sequences
+}
+
+// runs the command of the user with a shell, the notification is passed as
+// CHAT_EVENT, CHAT_NAME, CHAT_TEXT and CHAT_PLACE
+pub fn run_hook(command: &str, notification: &Notification) -> std::io::Result<Child> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    // the output would break the terminal UI
+    shell
+        .arg(command)
+        .env("CHAT_EVENT", notification.kind.to_string())
+        .env("CHAT_NAME", &notification.name)
+        .env("CHAT_TEXT", &notification.text)
+        .env("CHAT_PLACE", &notification.place)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
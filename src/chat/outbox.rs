@@ -0,0 +1,223 @@
+use crate::chat::replies::ReplyTo;
+use crate::config::settings;
+use crate::types::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Notify;
+
+const OUTBOX_FILE: &str = "outbox.json";
+
+// QueuedMessage is a chat message which could not be sent yet. The sender
+// fields are filled in when it is sent, they change with every registration.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct QueuedMessage {
+    pub id: u64,
+    pub plugin: String,
+    pub text: String,
+    // client id of the receiver of a private message
+    #[serde(default)]
+    pub receiver: String,
+    #[serde(default)]
+    pub message_id: String,
+    #[serde(default)]
+    pub reply_to: Option<ReplyTo>,
+    // failed messages are skipped until they are resent
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutboxState {
+    Queued,
+    Sent,
+    Failed,
+    Cancelled,
+}
+
+// OutboxUpdate is sent to the output with the OUTBOX_FLAG when a queued message changes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutboxUpdate {
+    pub state: OutboxState,
+    pub message: QueuedMessage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Queue {
+    next_id: u64,
+    messages: Vec<QueuedMessage>,
+}
+
+// Outbox keeps the chat messages which could not be sent in their order.
+// Without a file the messages are only kept while the client runs.
+#[derive(Debug, Default)]
+pub struct Outbox {
+    queue: Mutex<Queue>,
+    path: Mutex<Option<PathBuf>>,
+    // wakes the sender when a message is queued or the server is reachable again
+    pub changed: Notify,
+    // held while a message is posted, so a new message can not overtake a queued one
+    pub(crate) sending: tokio::sync::Mutex<()>,
+    // a disabled outbox queues nothing, sending to an unreachable server fails
+    disabled: AtomicBool,
+}
+
+impl Outbox {
+    pub fn new() -> Outbox {
+        Outbox::default()
+    }
+
+    // for clients which exit before a queued message could be sent, e.g. the headless mode
+    pub fn disable(&self) {
+        self.disabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.disabled.load(Ordering::SeqCst)
+    }
+
+    // the outbox file is stored next to config.toml
+    pub fn default_path() -> Option<PathBuf> {
+        settings::config_path().and_then(|path| path.parent().map(|dir| dir.join(OUTBOX_FILE)))
+    }
+
+    // Persist_to loads the messages of an earlier run from the file and keeps
+    // saving the queue there. Private messages are dropped, the client ids of
+    // their receivers changed with the restart.
+    pub fn persist_to(&self, path: PathBuf) -> Result<(), ConfigError> {
+        if path.exists() {
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|e| ConfigError::Io(format!("{}: {}", path.display(), e)))?;
+            let loaded: Queue = serde_json::from_str(&raw)
+                .map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))?;
+            let mut queue = self.lock();
+            for mut msg in loaded.messages {
+                if !msg.receiver.is_empty() {
+                    continue;
+                }
+                queue.next_id += 1;
+                msg.id = queue.next_id;
+                queue.messages.push(msg);
+            }
+        }
+        *self.path.lock().unwrap_or_else(|e| e.into_inner()) = Some(path);
+        self.save()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn save(&self) -> Result<(), ConfigError> {
+        let Some(path) = self.path.lock().unwrap_or_else(|e| e.into_inner()).clone() else {
+            return Ok(());
+        };
+        let queue = self.lock();
+        if queue.messages.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)
+                    .map_err(|e| ConfigError::Write(format!("{}: {}", path.display(), e)))?;
+            }
+            return Ok(());
+        }
+        let raw =
+            serde_json::to_string_pretty(&*queue).map_err(|e| ConfigError::Write(e.to_string()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| ConfigError::Write(format!("{}: {}", dir.display(), e)))?;
+        }
+        std::fs::write(&path, raw)
+            .map_err(|e| ConfigError::Write(format!("{}: {}", path.display(), e)))
+    }
+
+    // Push appends a message and returns it with its id
+    pub fn push(&self, mut msg: QueuedMessage) -> QueuedMessage {
+        {
+            let mut queue = self.lock();
+            queue.next_id += 1;
+            msg.id = queue.next_id;
+            queue.messages.push(msg.clone());
+        }
+        let _ = self.save();
+        self.changed.notify_one();
+        msg
+    }
+
+    // new messages have to wait while older ones are pending, so the order is kept
+    pub fn has_pending(&self) -> bool {
+        self.lock().messages.iter().any(|m| m.error.is_none())
+    }
+
+    // the oldest message which has not failed
+    pub fn next_pending(&self) -> Option<QueuedMessage> {
+        self.lock()
+            .messages
+            .iter()
+            .find(|m| m.error.is_none())
+            .cloned()
+    }
+
+    pub fn messages(&self) -> Vec<QueuedMessage> {
+        self.lock().messages.clone()
+    }
+
+    // the queued message with the given message id
+    pub fn find_message(&self, message_id: &str) -> Option<QueuedMessage> {
+        self.lock()
+            .messages
+            .iter()
+            .find(|m| m.message_id == message_id)
+            .cloned()
+    }
+
+    // Set_text changes a message before it is sent
+    pub fn set_text(&self, id: u64, text: &str) -> Option<QueuedMessage> {
+        let changed = {
+            let mut queue = self.lock();
+            let msg = queue.messages.iter_mut().find(|m| m.id == id)?;
+            msg.text = text.to_string();
+            msg.clone()
+        };
+        let _ = self.save();
+        Some(changed)
+    }
+
+    pub fn remove(&self, id: u64) -> Option<QueuedMessage> {
+        let removed = {
+            let mut queue = self.lock();
+            let index = queue.messages.iter().position(|m| m.id == id)?;
+            queue.messages.remove(index)
+        };
+        let _ = self.save();
+        Some(removed)
+    }
+
+    pub fn set_failed(&self, id: u64, error: String) -> Option<QueuedMessage> {
+        let failed = {
+            let mut queue = self.lock();
+            let msg = queue.messages.iter_mut().find(|m| m.id == id)?;
+            msg.error = Some(error);
+            msg.clone()
+        };
+        let _ = self.save();
+        Some(failed)
+    }
+
+    // Resend queues a failed message again at its old position
+    pub fn resend(&self, id: u64) -> Option<QueuedMessage> {
+        let resent = {
+            let mut queue = self.lock();
+            let msg = queue
+                .messages
+                .iter_mut()
+                .find(|m| m.id == id && m.error.is_some())?;
+            msg.error = None;
+            msg.clone()
+        };
+        let _ = self.save();
+        self.changed.notify_one();
+        Some(resent)
+    }
+}
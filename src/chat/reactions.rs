@@ -0,0 +1,130 @@
+use crate::chat::control;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Mutex;
+
+// Emoji reactions to chat messages. Like edits they are control messages to the
+// receivers of the original message: "[react:{id}] 👍" and "[unreact:{id}] 👍".
+// Every client counts the reactions itself, by the client ids of the reactors.
+
+const REACT_PREFIX: &str = "[react:";
+const UNREACT_PREFIX: &str = "[unreact:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reaction<'a> {
+    pub id: &'a str,
+    pub emoji: &'a str,
+    // false if the reaction is taken back
+    pub added: bool,
+}
+
+pub fn reaction(id: &str, emoji: &str, added: bool) -> String {
+    let prefix = if added { REACT_PREFIX } else { UNREACT_PREFIX };
+    control::control(&format!("{prefix}{id}] {emoji}"))
+}
+
+pub fn parse(content: &str) -> Option<Reaction<'_>> {
+    let content = control::strip(content)?;
+    let (rest, added) = match content.strip_prefix(REACT_PREFIX) {
+        Some(rest) => (rest, true),
+        None => (content.strip_prefix(UNREACT_PREFIX)?, false),
+    };
+    let (id, emoji) = rest.split_once(']')?;
+    let emoji = emoji.trim();
+    if id.is_empty() || emoji.is_empty() || emoji.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(Reaction { id, emoji, added })
+}
+
+// OwnReactions remembers the own reactions, reacting twice with the same emoji takes it back
+#[derive(Debug, Default)]
+pub struct OwnReactions {
+    reactions: Mutex<HashSet<(String, String)>>,
+}
+
+impl OwnReactions {
+    pub fn new() -> OwnReactions {
+        OwnReactions::default()
+    }
+
+    // Toggle returns whether the reaction was added
+    pub fn toggle(&self, id: &str, emoji: &str) -> bool {
+        let mut reactions = self.reactions.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (id.to_string(), emoji.to_string());
+        if reactions.remove(&key) {
+            return false;
+        }
+        reactions.insert(key);
+        true
+    }
+}
+
+// ReactionCount is one emoji under a message, `own` is set if the client reacted with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactionCount {
+    pub emoji: String,
+    pub count: usize,
+    pub own: bool,
+}
+
+// Reactions collects the reactions to the messages, the emojis keep the order
+// in which they were first used
+#[derive(Debug, Default)]
+pub struct Reactions {
+    messages: HashMap<String, Vec<(String, BTreeSet<String>)>>,
+}
+
+impl Reactions {
+    pub fn new() -> Reactions {
+        Reactions::default()
+    }
+
+    // Apply adds or removes the reaction of a client, returns false if nothing changed
+    pub fn apply(&mut self, reactor: &str, reaction: Reaction) -> bool {
+        if !reaction.added && !self.messages.contains_key(reaction.id) {
+            return false;
+        }
+        let emojis = self.messages.entry(reaction.id.to_string()).or_default();
+        let index = match emojis.iter().position(|(e, _)| e == reaction.emoji) {
+            Some(index) => index,
+            None if reaction.added => {
+                emojis.push((reaction.emoji.to_string(), BTreeSet::new()));
+                emojis.len() - 1
+            }
+            None => return false,
+        };
+
+        let reactors = &mut emojis[index].1;
+        let changed = if reaction.added {
+            reactors.insert(reactor.to_string())
+        } else {
+            reactors.remove(reactor)
+        };
+        if reactors.is_empty() {
+            emojis.remove(index);
+        }
+        if emojis.is_empty() {
+            self.messages.remove(reaction.id);
+        }
+        changed
+    }
+
+    pub fn counts(&self, id: &str, own_id: &str) -> Vec<ReactionCount> {
+        let Some(emojis) = self.messages.get(id) else {
+            return Vec::new();
+        };
+        emojis
+            .iter()
+            .map(|(emoji, reactors)| ReactionCount {
+                emoji: emoji.clone(),
+                count: reactors.len(),
+                own: reactors.contains(own_id),
+            })
+            .collect()
+    }
+
+    // keeps only the reactions to the messages for which `keep` is true
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.messages.retain(|id, _| keep(id));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::chat::control;
+use crate::chat::replies::ReplyTo;
+use serde::{Deserialize, Serialize};
+
+// Receipts of private messages. The server only forwards the content, so the id
+// of a message is appended to it behind an invisible separator and the receipts
+// are private control messages like "[ack:read:{id}]", which clients hide. Group messages
+// have their own separator, they get no receipts.
+
+// U+2063 INVISIBLE SEPARATOR
+const ID_SEPARATOR: &str = "\u{2063}#";
+const GROUP_ID_SEPARATOR: &str = "\u{2063}@";
+const RECEIPT_PREFIX: &str = "[ack:";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Sending,
+    Delivered,
+    Read,
+    Failed,
+}
+
+// DeliveryUpdate is sent to the output with the DELIVERY_FLAG when the state
+// of an own private message changes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeliveryUpdate {
+    pub id: String,
+    pub status: DeliveryStatus,
+    // only set while sending
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub reply_to: Option<ReplyTo>,
+}
+
+pub fn attach_id(text: &str, id: &str) -> String {
+    format!("{text}{ID_SEPARATOR}{id}")
+}
+
+pub fn attach_group_id(text: &str, id: &str) -> String {
+    format!("{text}{GROUP_ID_SEPARATOR}{id}")
+}
+
+fn split_at<'a>(content: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    content
+        .rsplit_once(separator)
+        .filter(|(_, id)| !id.is_empty() && !id.contains(char::is_whitespace))
+}
+
+// splits a received content into the text and the id of the message
+pub fn split_id(content: &str) -> (&str, Option<&str>) {
+    match split_at(content, ID_SEPARATOR).or_else(|| split_at(content, GROUP_ID_SEPARATOR)) {
+        Some((text, id)) => (text, Some(id)),
+        None => (content, None),
+    }
+}
+
+// only private messages are answered with receipts
+pub fn wants_receipt(content: &str) -> bool {
+    split_at(content, ID_SEPARATOR).is_some()
+}
+
+// the content of a receipt, only delivered and read are sent
+pub fn receipt(status: DeliveryStatus, id: &str) -> Option<String> {
+    let status = match status {
+        DeliveryStatus::Delivered => "delivered",
+        DeliveryStatus::Read => "read",
+        DeliveryStatus::Sending | DeliveryStatus::Failed => return None,
+    };
+    Some(control::control(&format!("{RECEIPT_PREFIX}{status}:{id}]")))
+}
+
+pub fn parse_receipt(content: &str) -> Option<(DeliveryStatus, &str)> {
+    let (status, id) = control::strip(content)?
+        .strip_prefix(RECEIPT_PREFIX)?
+        .strip_suffix(']')?
+        .split_once(':')?;
+    let status = match status {
+        "delivered" => DeliveryStatus::Delivered,
+        "read" => DeliveryStatus::Read,
+        _ => return None,
+    };
+    Some((status, id))
+}
@@ -0,0 +1,82 @@
+use crate::chat::control;
+use serde::{Deserialize, Serialize};
+
+// Replies to chat messages. The server only forwards the content, so the parent
+// of a reply is put in front of its text between invisible separators:
+// "\u{2063}>{id} {name}\u{2063}:{excerpt}\u{2063}<{text}". Clients without
+// replies show the excerpt as part of the text.
+
+const REPLY_START: &str = "\u{2063}>";
+const NAME_END: &str = "\u{2063}:";
+const REPLY_END: &str = "\u{2063}<";
+// number of chars of the parent which are quoted
+const EXCERPT_LEN: usize = 40;
+
+// ReplyTo is the message a reply refers to, with the name of its sender and
+// the beginning of its text
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReplyTo {
+    pub id: String,
+    pub name: String,
+    pub excerpt: String,
+}
+
+impl ReplyTo {
+    pub fn new(id: &str, name: &str, text: &str) -> ReplyTo {
+        ReplyTo {
+            id: id.to_string(),
+            name: clean(name),
+            excerpt: excerpt(text),
+        }
+    }
+}
+
+// the separators and line breaks are removed, they would break the reply
+fn clean(text: &str) -> String {
+    text.replace('\u{2063}', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// the beginning of a text, long ones are cut off with "…"
+pub fn excerpt(text: &str) -> String {
+    let text = clean(text);
+    if text.chars().count() <= EXCERPT_LEN {
+        return text;
+    }
+    let mut excerpt: String = text.chars().take(EXCERPT_LEN - 1).collect();
+    excerpt.push('…');
+    excerpt
+}
+
+pub fn attach_reply(text: &str, reply_to: &ReplyTo) -> String {
+    format!(
+        "{REPLY_START}{} {}{NAME_END}{}{REPLY_END}{text}",
+        reply_to.id, reply_to.name, reply_to.excerpt
+    )
+}
+
+// splits a received text into the parent and the text of the reply, the
+// text is unescaped
+pub fn split_reply(content: &str) -> (Option<ReplyTo>, &str) {
+    let Some((reply, text)) = content
+        .strip_prefix(REPLY_START)
+        .and_then(|rest| rest.split_once(REPLY_END))
+    else {
+        return (None, control::unescape(content));
+    };
+    let Some((id, name, excerpt)) = reply
+        .split_once(NAME_END)
+        .and_then(|(parent, excerpt)| parent.split_once(' ').map(|(id, name)| (id, name, excerpt)))
+        .filter(|(id, _, _)| !id.is_empty())
+    else {
+        return (None, control::unescape(content));
+    };
+    let reply_to = ReplyTo {
+        id: id.to_string(),
+        name: name.to_string(),
+        excerpt: excerpt.to_string(),
+    };
+    (Some(reply_to), control::unescape(text))
+}
@@ -0,0 +1,109 @@
+use clap::builder::PossibleValuesParser;
+use clap::{ArgMatches, Parser};
+use rust_chat_client::api::client::DEFAULT_SERVER_URL;
+use rust_chat_client::bots::example_bots::BOTS;
+
+// Args are the command line arguments. Without --name or --bridge the terminal UI is
+// started, with --name the client runs headless and executes the given steps in order.
+#[derive(Parser, Debug, Clone)]
+#[command(version, about = "Terminal client for the Go-Chat-App server")]
+pub struct Args {
+    /// Url of the chat server
+    #[arg(long, value_name = "URL", default_value = DEFAULT_SERVER_URL)]
+    pub server: String,
+
+    /// Runs without the terminal UI and registers with this name
+    #[arg(long, value_name = "NAME")]
+    pub name: Option<String>,
+
+    /// Broadcasts a message, can be repeated
+    #[arg(long, value_name = "TEXT", requires = "name")]
+    pub send: Vec<String>,
+
+    /// Sends a private message to a user, can be repeated
+    #[arg(long, num_args = 2, value_names = ["USER", "TEXT"], requires = "name")]
+    pub private: Vec<String>,
+
+    /// Executes a command e.g. "/group join ops", can be repeated
+    #[arg(long, value_name = "COMMAND", requires = "name")]
+    pub exec: Vec<String>,
+
+    /// Prints incoming messages for this many seconds before exiting
+    #[arg(long, value_name = "SECONDS", requires = "name")]
+    pub wait: Option<u64>,
+
+    /// Stops waiting once this many replies have been received
+    #[arg(long, value_name = "COUNT", requires = "wait")]
+    pub replies: Option<usize>,
+
+    /// Runs a bot until Ctrl+C is pressed, can be repeated
+    #[arg(
+        long,
+        value_name = "BOT",
+        value_parser = PossibleValuesParser::new(BOTS),
+        requires = "name",
+        conflicts_with_all = ["send", "private", "exec", "wait", "bridge"]
+    )]
+    pub bot: Vec<String>,
+
+    /// Reads commands as JSON lines from stdin and writes responses as JSON lines to stdout
+    #[arg(long, conflicts_with_all = ["send", "private", "exec", "wait"])]
+    pub bridge: bool,
+}
+
+// Step is one command of the headless mode
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    Send(String),
+    Private { user: String, text: String },
+    Exec(String),
+}
+
+impl Args {
+    // parses the arguments and returns the steps in the order they were given
+    pub fn parse_with_steps() -> (Args, Vec<Step>) {
+        let matches = <Args as clap::CommandFactory>::command().get_matches();
+        let args =
+            <Args as clap::FromArgMatches>::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        let steps = steps(&matches);
+        (args, steps)
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.name.is_some() && !self.bridge
+    }
+}
+
+fn steps(matches: &ArgMatches) -> Vec<Step> {
+    let mut steps: Vec<(usize, Step)> = Vec::new();
+
+    for (index, text) in indexed(matches, "send") {
+        steps.push((index, Step::Send(text)));
+    }
+    for (index, text) in indexed(matches, "exec") {
+        steps.push((index, Step::Exec(text)));
+    }
+    // --private takes two values, the index of the user marks the position
+    let private = indexed(matches, "private");
+    for pair in private.chunks(2) {
+        if let [(index, user), (_, text)] = pair {
+            steps.push((
+                *index,
+                Step::Private {
+                    user: user.clone(),
+                    text: text.clone(),
+                },
+            ));
+        }
+    }
+
+    steps.sort_by_key(|(index, _)| *index);
+    steps.into_iter().map(|(_, step)| step).collect()
+}
+
+fn indexed(matches: &ArgMatches, id: &str) -> Vec<(usize, String)> {
+    match (matches.indices_of(id), matches.get_many::<String>(id)) {
+        (Some(indices), Some(values)) => indices.zip(values.cloned()).collect(),
+        _ => Vec::new(),
+    }
+}
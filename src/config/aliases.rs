@@ -0,0 +1,249 @@
+use crate::config::settings;
+use crate::i18n::catalog::tr_args;
+use crate::plugins::plugin_registry::{ArgKind, PluginInfo};
+use crate::types::{ChatErrorWithMsg, ConfigError};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+const ALIAS_FILE: &str = "aliases.toml";
+// separates the steps of a macro, "\;" is a ; inside a step
+const STEP_SEPARATOR: char = ';';
+const ESCAPE: char = '\\';
+
+// UserAliases are user-defined commands which expand to one or more inputs.
+// The terminal client stores them next to config.toml, without a file they are
+// only kept while the client runs:
+//
+//     "/hi" = "/group join $1; hello $*"
+//
+// $1 to $9 are replaced by the arguments, $* by all arguments.
+// A step which contains a ; escapes it as \;.
+#[derive(Debug)]
+pub struct UserAliases {
+    path: Option<PathBuf>,
+    aliases: RwLock<BTreeMap<String, String>>,
+    // commands which aliases must not replace
+    reserved: RwLock<Vec<String>>,
+}
+
+impl UserAliases {
+    pub fn new() -> UserAliases {
+        UserAliases {
+            path: None,
+            aliases: RwLock::new(BTreeMap::new()),
+            reserved: RwLock::new(Vec::new()),
+        }
+    }
+
+    // the aliases are read from and saved to the given file
+    pub fn with_path(path: PathBuf) -> UserAliases {
+        UserAliases {
+            path: Some(path),
+            ..UserAliases::new()
+        }
+    }
+
+    // the alias file next to config.toml
+    pub fn default_path() -> Option<PathBuf> {
+        settings::config_path().and_then(|path| path.parent().map(|dir| dir.join(ALIAS_FILE)))
+    }
+
+    pub fn reserve(&self, command: &str) {
+        self.reserved
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(command.to_string());
+    }
+
+    pub fn is_reserved(&self, command: &str) -> bool {
+        self.reserved
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|c| c == command)
+    }
+
+    // Load reads the alias file, a missing file means no aliases
+    pub fn load(&self) -> Result<(), ConfigError> {
+        let Some(path) = self.path.as_ref().filter(|path| path.exists()) else {
+            return Ok(());
+        };
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::Io(format!("{}: {}", path.display(), e)))?;
+        let aliases: BTreeMap<String, String> = toml::from_str(&raw)
+            .map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))?;
+        *self.aliases.write().unwrap_or_else(|e| e.into_inner()) = aliases;
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), ConfigError> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        let raw = toml::to_string(&*self.aliases.read().unwrap_or_else(|e| e.into_inner()))
+            .map_err(|e| ConfigError::Write(e.to_string()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| ConfigError::Write(format!("{}: {}", dir.display(), e)))?;
+        }
+        std::fs::write(path, raw)
+            .map_err(|e| ConfigError::Write(format!("{}: {}", path.display(), e)))
+    }
+
+    // Set adds or replaces an alias and saves all aliases
+    pub fn set(&self, name: &str, expansion: &str) -> Result<(), ConfigError> {
+        self.aliases
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), expansion.to_string());
+        self.save()
+    }
+
+    // Remove returns false if there is no such alias
+    pub fn remove(&self, name: &str) -> Result<bool, ConfigError> {
+        let removed = self
+            .aliases
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(name)
+            .is_some();
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    // all aliases sorted by name
+    pub fn all(&self) -> Vec<(String, String)> {
+        self.aliases
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(name, expansion)| (name.clone(), expansion.clone()))
+            .collect()
+    }
+
+    pub fn infos(&self) -> Vec<PluginInfo> {
+        self.all()
+            .iter()
+            .map(|(name, expansion)| info(name, expansion))
+            .collect()
+    }
+
+    // Expand returns the inputs of an alias with the arguments filled in,
+    // None if the command is no alias
+    pub fn expand(&self, name: &str, args: &str) -> Option<Result<Vec<String>, ChatErrorWithMsg>> {
+        let expansion = self
+            .aliases
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()?;
+
+        if let Err(e) = info(name, &expansion).check(args) {
+            return Some(Err(e));
+        }
+        let words: Vec<&str> = args.split_whitespace().collect();
+        Some(Ok(split_steps(&expansion)
+            .iter()
+            .map(|step| substitute(step.trim(), &words, args.trim()))
+            .filter(|step| !step.is_empty())
+            .collect()))
+    }
+}
+
+impl Default for UserAliases {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// an alias name is a command like /hi
+pub fn valid_name(name: &str) -> bool {
+    name.strip_prefix('/').is_some_and(|rest| {
+        !rest.is_empty()
+            && rest
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+// splits an expansion into its steps, escaped separators are kept in the step
+pub fn split_steps(expansion: &str) -> Vec<String> {
+    let mut steps = Vec::new();
+    let mut step = String::new();
+    let mut chars = expansion.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE if chars.peek() == Some(&STEP_SEPARATOR) => {
+                chars.next();
+                step.push(STEP_SEPARATOR);
+            }
+            STEP_SEPARATOR => steps.push(std::mem::take(&mut step)),
+            _ => step.push(c),
+        }
+    }
+    steps.push(step);
+    steps
+}
+
+// the arguments of an alias follow from the parameters of its expansion
+fn info(name: &str, expansion: &str) -> PluginInfo {
+    let (count, rest) = parameters(expansion);
+    let mut info = PluginInfo::new(
+        name,
+        tr_args("alias-help", &[("expansion", expansion.to_string())]),
+    );
+    for i in 1..=count {
+        info = info.arg(i.to_string(), ArgKind::Word);
+    }
+    if rest {
+        info = info.optional_arg("args", ArgKind::Text);
+    }
+    info
+}
+
+// returns the highest $n and whether $* is used
+fn parameters(expansion: &str) -> (usize, bool) {
+    let mut count = 0;
+    let mut rest = false;
+    let mut chars = expansion.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            continue;
+        }
+        match chars.peek() {
+            Some('*') => rest = true,
+            Some(d) if d.is_ascii_digit() && *d != '0' => {
+                count = count.max(d.to_digit(10).unwrap_or(0) as usize);
+            }
+            _ => {}
+        }
+    }
+    (count, rest)
+}
+
+fn substitute(step: &str, words: &[&str], all: &str) -> String {
+    let mut result = String::new();
+    let mut chars = step.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+        match chars.peek().copied() {
+            Some('*') => {
+                chars.next();
+                result.push_str(all);
+            }
+            Some(d) if d.is_ascii_digit() && d != '0' => {
+                chars.next();
+                let i = d.to_digit(10).unwrap_or(0) as usize;
+                result.push_str(words.get(i - 1).copied().unwrap_or_default());
+            }
+            _ => result.push(c),
+        }
+    }
+    result.trim().to_string()
+}
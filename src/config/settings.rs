@@ -0,0 +1,110 @@
+use crate::chat::notifications::{DesktopNotification, NotificationKind};
+use crate::types::ConfigError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "rust-chat-client";
+const CONFIG_FILE: &str = "config.toml";
+// overrides the location of the config file
+const CONFIG_ENV: &str = "CHAT_CLIENT_CONFIG";
+
+// Settings holds everything the user can configure in config.toml.
+// Missing sections fall back to their defaults.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Settings {
+    // name of a preset or of a file in the themes directory
+    pub theme: Option<String>,
+    // e.g. "de" or "en", falls back to LANG
+    pub locale: Option<String>,
+    // number of chat lines which are kept, older ones are dropped
+    pub max_messages: Option<usize>,
+    // links in the chat are written as OSC 8 hyperlinks, on by default
+    pub hyperlinks: Option<bool>,
+    // the initials of the users are shown in a colored badge in front of their names
+    pub name_badges: Option<bool>,
+    // scope ("global", "chat", "mentions", "users", "help") -> action -> keys
+    pub keymap: HashMap<String, HashMap<String, KeyList>>,
+    // middleware name -> enabled, overrides the default of the middleware
+    pub middleware: HashMap<String, bool>,
+    pub notifications: NotificationSettings,
+}
+
+// how the user is told about private messages, mentions and calls
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub events: Vec<NotificationKind>,
+    pub bell: bool,
+    pub desktop: DesktopNotification,
+    // number of notifications in the window title until the terminal gets the focus
+    pub title: bool,
+    // shell command which is run on every notification
+    pub command: Option<String>,
+    // terminals without focus reporting always count as focused
+    pub when_focused: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            events: vec![
+                NotificationKind::Private,
+                NotificationKind::Mention,
+                NotificationKind::Call,
+            ],
+            bell: true,
+            desktop: DesktopNotification::Off,
+            title: true,
+            command: None,
+            when_focused: false,
+        }
+    }
+}
+
+// a single key or a list of keys bound to one action
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> Vec<String> {
+        match self {
+            KeyList::One(key) => vec![key.clone()],
+            KeyList::Many(keys) => keys.clone(),
+        }
+    }
+}
+
+impl Settings {
+    // Load reads the config file, a missing file results in the default settings
+    pub fn load() -> Result<Settings, ConfigError> {
+        let path = match config_path() {
+            Some(p) => p,
+            None => return Ok(Settings::default()),
+        };
+        if !path.exists() {
+            return Ok(Settings::default());
+        }
+
+        let raw = std::fs::read_to_string(&path)
+            .map_err(|e| ConfigError::Io(format!("{}: {}", path.display(), e)))?;
+        toml::from_str(&raw).map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))
+    }
+}
+
+// directory which contains config.toml and all other user files
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
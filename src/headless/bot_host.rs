@@ -0,0 +1,46 @@
+use crate::headless::runner::{EXIT_NOT_REGISTERED, EXIT_OK};
+use rust_chat_client::Client;
+use rust_chat_client::bots::bot_registry::BotRegistry;
+use rust_chat_client::bots::example_bots::bot_by_name;
+use std::collections::HashMap;
+use std::process::ExitCode;
+
+// BotHost registers and runs the given bots until Ctrl+C is pressed
+pub struct BotHost {
+    client: Client,
+}
+
+impl BotHost {
+    pub async fn new(server_url: String, middleware: &HashMap<String, bool>) -> BotHost {
+        let mut builder = Client::builder(server_url);
+        for (name, enabled) in middleware {
+            builder = builder.middleware_enabled(name, *enabled);
+        }
+        BotHost {
+            client: builder.build().await,
+        }
+    }
+
+    pub async fn run(mut self, name: &str, bots: &[String]) -> ExitCode {
+        if let Err(e) = self.client.register(name).await {
+            eprintln!("{}: {}", e.kind, e.msg);
+            return ExitCode::from(EXIT_NOT_REGISTERED);
+        }
+
+        let mut registry = BotRegistry::new(self.client.user_service().chat_client.clone());
+        // the names have already been checked by clap
+        for bot in bots.iter().filter_map(|name| bot_by_name(name)) {
+            registry.add(bot);
+        }
+
+        if let Some(events) = self.client.events() {
+            tokio::select! {
+                _ = registry.run(events) => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+
+        let _ = self.client.quit().await;
+        ExitCode::from(EXIT_OK)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::headless::runner::{EXIT_COMMAND_FAILED, EXIT_NOT_REGISTERED, EXIT_OK};
+use rust_chat_client::chat::chat_client::ChatClient;
+use rust_chat_client::plugins::plugin_registry::PluginConfig;
+use rust_chat_client::service::user_service::UserService;
+use rust_chat_client::types::{
+    ADD_GROUP_FLAG, IGNORE_RESPONSE_TAG, JsonClient, LEAVE_GROUP_FLAG, Response, USERS_FLAG,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc::Receiver;
+
+// BridgeCommand is one JSON line on stdin e.g.
+// {"type": "send", "text": "hello"} or {"type": "command", "input": "/group join ops"}
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BridgeCommand {
+    // any input the TUI accepts
+    Command { input: String },
+    Send { text: String },
+    // user may be a name or a client id
+    Private { user: String, text: String },
+    Quit,
+}
+
+// Bridge reads commands as JSON lines from stdin and writes every response
+// as a JSON line to stdout, so bots can be written in any language
+pub struct Bridge {
+    user_service: Arc<UserService>,
+    receiver: Receiver<Response>,
+    // name -> client_id
+    known_clients: HashMap<String, String>,
+}
+
+impl Bridge {
+    pub async fn new(server_url: String, config: PluginConfig) -> Bridge {
+        let (tx, receiver) = tokio::sync::mpsc::channel(1000);
+        let chat_client = Arc::new(ChatClient::new_client(server_url, tx).await);
+        Bridge {
+            user_service: Arc::new(UserService::new_user_service(chat_client, config)),
+            receiver,
+            known_clients: HashMap::new(),
+        }
+    }
+
+    // without a name the first command has to be "/register {name}"
+    pub async fn run(mut self, name: Option<String>) -> ExitCode {
+        if let Some(name) = name
+            && let Err(e) = self
+                .user_service
+                .execute(&format!("/register {name}"))
+                .await
+        {
+            write_line(&Response::error(format!("{}: {}", e.kind, e.msg)));
+            return ExitCode::from(EXIT_NOT_REGISTERED);
+        }
+
+        // the poller waits until the client is registered
+        let chat_client = self.user_service.chat_client.clone();
+        tokio::spawn(async move {
+            chat_client.response_poller().await;
+        });
+
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let code = loop {
+            tokio::select! {
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => {
+                        if !self.handle_line(&line).await {
+                            break EXIT_OK;
+                        }
+                    }
+                    // stdin has been closed
+                    Ok(None) => break EXIT_OK,
+                    Err(_) => break EXIT_COMMAND_FAILED,
+                },
+                Some(rsp) = self.receiver.recv() => self.handle_response(rsp).await,
+            }
+        };
+
+        if *self.user_service.chat_client.registered.lock().await {
+            let _ = self.user_service.execute("/quit").await;
+        }
+        ExitCode::from(code)
+    }
+
+    // returns false if the bridge should stop
+    async fn handle_line(&mut self, line: &str) -> bool {
+        if line.trim().is_empty() {
+            return true;
+        }
+        let command: BridgeCommand = match serde_json::from_str(line) {
+            Ok(c) => c,
+            Err(e) => {
+                write_line(&Response::error(format!("Invalid command: {e}")));
+                return true;
+            }
+        };
+
+        let input = match command {
+            BridgeCommand::Command { input } => input,
+            BridgeCommand::Send { text } => format!("/broadcast {text}"),
+            BridgeCommand::Private { user, text } => {
+                let client_id = self.known_clients.get(&user).cloned().unwrap_or(user);
+                format!("/private {client_id} {text}")
+            }
+            BridgeCommand::Quit => return false,
+        };
+        // the result is sent to the output channel like in the TUI
+        self.user_service.executor(&input).await;
+        true
+    }
+
+    async fn handle_response(&mut self, rsp: Response) {
+        if rsp.err == IGNORE_RESPONSE_TAG || (rsp.content.is_empty() && rsp.err.is_empty()) {
+            return;
+        }
+
+        // the client state is kept up to date like in the TUI
+        match rsp.rsp_name.as_str() {
+            ADD_GROUP_FLAG => {
+                if let Err(e) = self
+                    .user_service
+                    .chat_client
+                    .handle_add_group(rsp.content.clone())
+                    .await
+                {
+                    write_line(&Response::error(format!("{}: {}", e.kind, e.msg)));
+                }
+            }
+            LEAVE_GROUP_FLAG => *self.user_service.chat_client.group.lock().await = None,
+            USERS_FLAG => {
+                let users: Vec<JsonClient> = serde_json::from_str(&rsp.content).unwrap_or_default();
+                for user in users.iter() {
+                    self.known_clients
+                        .insert(user.name.clone(), user.client_id.clone());
+                }
+                *self.user_service.chat_client.users.lock().await = users;
+            }
+            _ if !rsp.client_id.is_empty() => {
+                self.known_clients
+                    .insert(rsp.rsp_name.clone(), rsp.client_id.clone());
+            }
+            _ => {}
+        }
+
+        write_line(&rsp);
+    }
+}
+
+fn write_line(rsp: &Response) {
+    if let Ok(json) = serde_json::to_string(rsp) {
+        println!("{json}");
+    }
+}
@@ -0,0 +1,3 @@
+pub mod bot_host;
+pub mod bridge;
+pub mod runner;
@@ -0,0 +1,241 @@
+use crate::cli::{Args, Step};
+use rust_chat_client::chat::chat_client::ChatClient;
+use rust_chat_client::chat::control;
+use rust_chat_client::chat::receipts;
+use rust_chat_client::chat::replies;
+use rust_chat_client::i18n::catalog::{tr, tr_args};
+use rust_chat_client::plugins::plugin_registry::PluginConfig;
+use rust_chat_client::service::user_service::UserService;
+use rust_chat_client::types::{
+    ADD_GROUP_FLAG, ChatError, ChatErrorWithMsg, DELIVERY_FLAG, EDIT_FLAG, GROUP_HELP_FLAG,
+    HELP_FLAG, IGNORE_RESPONSE_TAG, JsonClient, LEAVE_GROUP_FLAG, OUTBOX_FLAG, Response,
+    USER_ADD_FLAG, USER_REMOVE_FLAG, USERS_FLAG,
+};
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::Receiver;
+use tokio::time::{Instant, timeout_at};
+
+// exit codes of the headless mode, 2 is used by clap for invalid arguments
+pub const EXIT_OK: u8 = 0;
+pub const EXIT_COMMAND_FAILED: u8 = 1;
+pub const EXIT_NOT_REGISTERED: u8 = 3;
+pub const EXIT_TIMEOUT: u8 = 4;
+
+// how long the runner waits for the user list when resolving a name
+const USERS_TIMEOUT: Duration = Duration::from_secs(5);
+// commands like /group join are answered by the server through the poller,
+// the runner waits until it is quiet before the next step is executed
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+// HeadlessRunner registers, executes the steps of the command line and prints
+// everything the server sends to stdout, errors go to stderr
+pub struct HeadlessRunner {
+    user_service: Arc<UserService>,
+    receiver: Receiver<Response>,
+    // name -> client_id
+    known_clients: HashMap<String, String>,
+    replies: usize,
+}
+
+impl HeadlessRunner {
+    pub async fn new(server_url: String, config: PluginConfig) -> HeadlessRunner {
+        let (tx, receiver) = tokio::sync::mpsc::channel(1000);
+        let chat_client = Arc::new(ChatClient::new_client(server_url, tx).await);
+        // the queue is lost when the runner exits, so a message which can't be sent fails the step
+        chat_client.outbox.disable();
+        HeadlessRunner {
+            user_service: Arc::new(UserService::new_user_service(chat_client, config)),
+            receiver,
+            known_clients: HashMap::new(),
+            replies: 0,
+        }
+    }
+
+    pub async fn run(mut self, args: Args, steps: Vec<Step>) -> ExitCode {
+        let name = args.name.unwrap_or_default();
+        if let Err(e) = self
+            .user_service
+            .execute(&format!("/register {name}"))
+            .await
+        {
+            print_error(&e);
+            return ExitCode::from(EXIT_NOT_REGISTERED);
+        }
+
+        let chat_client = self.user_service.chat_client.clone();
+        tokio::spawn(async move {
+            chat_client.response_poller().await;
+        });
+
+        let mut code = EXIT_OK;
+        for step in steps {
+            if let Err(e) = self.execute_step(step).await {
+                print_error(&e);
+                code = EXIT_COMMAND_FAILED;
+                break;
+            }
+        }
+
+        if code == EXIT_OK
+            && let Some(seconds) = args.wait
+            && !self.wait_for_replies(seconds, args.replies).await
+        {
+            code = EXIT_TIMEOUT;
+        }
+
+        let _ = self.user_service.execute("/quit").await;
+        ExitCode::from(code)
+    }
+
+    async fn execute_step(&mut self, step: Step) -> Result<(), ChatErrorWithMsg> {
+        let input = match step {
+            Step::Send(text) => format!("/broadcast {text}"),
+            Step::Exec(command) => command,
+            Step::Private { user, text } => {
+                let client_id = self.resolve_user(&user).await?;
+                format!("/private {client_id} {text}")
+            }
+        };
+
+        let output = self.user_service.execute(&input).await?;
+        if !output.is_empty() {
+            println!("{output}");
+        }
+        self.settle().await;
+        Ok(())
+    }
+
+    // looks up the client id of a user, the list is requested from the server once
+    async fn resolve_user(&mut self, name: &str) -> Result<String, ChatErrorWithMsg> {
+        if !self.known_clients.contains_key(name) {
+            self.user_service.execute("/users").await?;
+            let deadline = Instant::now() + USERS_TIMEOUT;
+            while let Ok(Some(rsp)) = timeout_at(deadline, self.receiver.recv()).await {
+                if rsp.rsp_name == USERS_FLAG {
+                    let users: Vec<JsonClient> =
+                        serde_json::from_str(&rsp.content).unwrap_or_default();
+                    for user in users {
+                        self.known_clients.insert(user.name, user.client_id);
+                    }
+                    break;
+                }
+                self.print_response(rsp).await;
+            }
+        }
+
+        self.known_clients.get(name).cloned().ok_or_else(|| {
+            ChatErrorWithMsg::new(
+                ChatError::NotAvailable,
+                tr_args("headless-unknown-user", &[("name", name.to_string())]),
+            )
+        })
+    }
+
+    // prints responses until none arrived for SETTLE_TIME, empty polls don't count
+    async fn settle(&mut self) {
+        let mut deadline = Instant::now() + SETTLE_TIME;
+        while let Ok(Some(rsp)) = timeout_at(deadline, self.receiver.recv()).await {
+            if rsp.err != IGNORE_RESPONSE_TAG {
+                deadline = Instant::now() + SETTLE_TIME;
+            }
+            self.print_response(rsp).await;
+        }
+    }
+
+    // prints responses for the given time, returns false if less than the expected
+    // number of replies arrived. Replies received while the steps ran are counted too.
+    async fn wait_for_replies(&mut self, seconds: u64, expected: Option<usize>) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(seconds);
+        loop {
+            if expected.is_some_and(|n| self.replies >= n) {
+                return true;
+            }
+            match timeout_at(deadline, self.receiver.recv()).await {
+                Ok(Some(rsp)) => self.print_response(rsp).await,
+                // channel closed or deadline reached
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        match expected {
+            Some(n) if self.replies < n => {
+                eprintln!(
+                    "{}",
+                    tr_args(
+                        "headless-timeout",
+                        &[
+                            ("received", self.replies.to_string()),
+                            ("expected", n.to_string()),
+                        ],
+                    )
+                );
+                false
+            }
+            _ => true,
+        }
+    }
+
+    async fn print_response(&mut self, rsp: Response) {
+        match rsp {
+            Response { err, .. } if !err.is_empty() => {
+                if err != IGNORE_RESPONSE_TAG {
+                    eprintln!("{err}");
+                }
+            }
+            // typing notifications, receipts, edits and reactions are no replies
+            Response { content, .. } if content.is_empty() || control::is_control(&content) => {}
+            Response { rsp_name, .. }
+                if rsp_name == DELIVERY_FLAG
+                    || rsp_name == EDIT_FLAG
+                    || rsp_name == OUTBOX_FLAG => {}
+            // the json of the tables is printed unchanged, so it can be piped to jq
+            Response { rsp_name, .. }
+                if rsp_name == USERS_FLAG
+                    || rsp_name == HELP_FLAG
+                    || rsp_name == GROUP_HELP_FLAG =>
+            {
+                println!("{}", rsp.content);
+            }
+            Response { rsp_name, .. } if rsp_name.is_empty() => println!("{}", rsp.content),
+            Response { rsp_name, .. } if rsp_name == USER_ADD_FLAG => {
+                println!("{}", tr_args("user-joined", &[("name", rsp.content)]));
+            }
+            Response { rsp_name, .. } if rsp_name == USER_REMOVE_FLAG => {
+                println!("{}", tr_args("user-left", &[("name", rsp.content)]));
+            }
+            Response { rsp_name, .. } if rsp_name == ADD_GROUP_FLAG => {
+                // the group is stored, so following messages are sent to it
+                match self
+                    .user_service
+                    .chat_client
+                    .handle_add_group(rsp.content)
+                    .await
+                {
+                    Ok(group) => println!("{}", tr_args("group-joined", &[("group", group.name)])),
+                    Err(e) => print_error(&e),
+                }
+            }
+            Response { rsp_name, .. } if rsp_name == LEAVE_GROUP_FLAG => {
+                *self.user_service.chat_client.group.lock().await = None;
+                println!("{}", tr("group-left"));
+            }
+            _ => {
+                let own_id = self.user_service.chat_client.client_id.lock().await.clone();
+                if !rsp.client_id.is_empty() && rsp.client_id != own_id {
+                    self.known_clients
+                        .insert(rsp.rsp_name.clone(), rsp.client_id.clone());
+                    self.replies += 1;
+                }
+                let (_, text) = replies::split_reply(receipts::split_id(&rsp.content).0);
+                println!("{}: {text}", rsp.rsp_name);
+            }
+        }
+    }
+}
+
+fn print_error(e: &ChatErrorWithMsg) {
+    eprintln!("{}: {}", e.kind, e.msg);
+}
@@ -17,3 +17,28 @@ pub fn lines_from_string(s: &str) -> Vec<Line<'static>> {
         .map(|line| Line::from(line.to_owned()))
         .collect()
 }
+
+// FNV-1a hash of a text, it is the same on every client and in every run
+pub fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// up to two letters of a name, e.g. "JD" for "john_doe" and "AL" for "alice"
+pub fn initials(name: &str) -> String {
+    let words: Vec<&str> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let initials: String = match words.as_slice() {
+        [] => String::from("?"),
+        [word] => word.chars().take(2).collect(),
+        [first, second, ..] => first
+            .chars()
+            .take(1)
+            .chain(second.chars().take(1))
+            .collect(),
+    };
+    initials.to_uppercase()
+}
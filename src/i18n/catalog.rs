@@ -0,0 +1,179 @@
+use crate::config::settings;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+pub const DEFAULT_LOCALE: &str = "de";
+// used for keys which are missing in the selected catalog
+const FALLBACK_LOCALE: &str = "en";
+
+// catalogs which are compiled into the binary,
+// further catalogs can be put into <config dir>/locales/<locale>.ftl
+const BUILTIN_CATALOGS: [(&str, &str); 2] = [
+    ("de", include_str!("../../locales/de.ftl")),
+    ("en", include_str!("../../locales/en.ftl")),
+];
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+// Catalog contains the bundle of the selected locale and the fallback bundle
+pub struct Catalog {
+    bundle: Option<FluentBundle<FluentResource>>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Catalog {
+    fn load(locale: &str) -> Catalog {
+        let fallback = load_bundle(FALLBACK_LOCALE)
+            .unwrap_or_else(|| FluentBundle::new_concurrent(Vec::new()));
+        Catalog {
+            bundle: load_bundle(locale),
+            fallback,
+        }
+    }
+
+    fn format(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in self.bundle.iter().chain(std::iter::once(&self.fallback)) {
+            let Some(pattern) = bundle.get_message(key).and_then(|m| m.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned();
+        }
+        key.to_string()
+    }
+}
+
+fn load_bundle(locale: &str) -> Option<FluentBundle<FluentResource>> {
+    let user_catalog = settings::config_dir()
+        .map(|dir| dir.join("locales").join(format!("{locale}.ftl")))
+        .and_then(|path| std::fs::read_to_string(path).ok());
+    let source = match user_catalog {
+        Some(source) => source,
+        None => BUILTIN_CATALOGS
+            .iter()
+            .find(|(l, _)| *l == locale)
+            .map(|(_, source)| source.to_string())?,
+    };
+
+    // broken entries are skipped by fluent, the rest of the catalog is still usable
+    let resource = match FluentResource::try_new(source) {
+        Ok(r) => r,
+        Err((r, _)) => r,
+    };
+    let lang_id: LanguageIdentifier = locale.parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
+    // isolation marks are rendered as garbage by most terminals
+    bundle.set_use_isolating(false);
+    let _ = bundle.add_resource(resource);
+    Some(bundle)
+}
+
+// picks the locale out of the config, then LC_ALL, LC_MESSAGES and LANG, then the default
+fn select_locale(configured: Option<&str>, env: impl Fn(&str) -> Option<String>) -> String {
+    if let Some(locale) = configured {
+        return locale.to_string();
+    }
+    for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        if let Some(value) = env(var) {
+            // e.g. "de_DE.UTF-8" -> "de"
+            let lang = value.split(['_', '.', '@']).next().unwrap_or_default();
+            if !lang.is_empty() && lang != "C" && lang != "POSIX" {
+                return lang.to_lowercase();
+            }
+        }
+    }
+    DEFAULT_LOCALE.to_string()
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+// Init selects the catalog, it has to be called before the first translation
+pub fn init(configured: Option<&str>) {
+    let _ = CATALOG.set(Catalog::load(&select_locale(configured, env_var)));
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| Catalog::load(&select_locale(None, env_var)))
+}
+
+// Tr returns the translation of a key, unknown keys are returned unchanged
+pub fn tr(key: &str) -> String {
+    catalog().format(key, None)
+}
+
+// TrCount returns the translation of a key with $count set, which allows plural forms
+pub fn tr_count(key: &str, count: usize) -> String {
+    let mut fluent_args = FluentArgs::new();
+    fluent_args.set("count", count);
+    catalog().format(key, Some(&fluent_args))
+}
+
+// TrArgs returns the translation of a key with the given arguments inserted
+pub fn tr_args(key: &str, args: &[(&str, String)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    catalog().format(key, Some(&fluent_args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn the_configured_locale_comes_first() {
+        let vars = [("LC_ALL", "de_DE.UTF-8")];
+        assert_eq!(select_locale(Some("en"), env(&vars)), "en");
+    }
+
+    #[test]
+    fn locale_variables_are_read_in_order() {
+        let all = [
+            ("LC_ALL", "fr_FR.UTF-8"),
+            ("LC_MESSAGES", "de_DE"),
+            ("LANG", "en_US.UTF-8"),
+        ];
+        assert_eq!(select_locale(None, env(&all)), "fr");
+        assert_eq!(select_locale(None, env(&all[1..])), "de");
+        assert_eq!(select_locale(None, env(&all[2..])), "en");
+    }
+
+    #[test]
+    fn c_and_posix_locales_are_skipped() {
+        let vars = [
+            ("LC_ALL", "C"),
+            ("LC_MESSAGES", "POSIX"),
+            ("LANG", "de_AT@euro"),
+        ];
+        assert_eq!(select_locale(None, env(&vars)), "de");
+        assert_eq!(select_locale(None, env(&vars[..2])), DEFAULT_LOCALE);
+        assert_eq!(select_locale(None, env(&[])), DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn missing_translations_fall_back_to_english() {
+        let english = Catalog::load("en").format("help-private", None);
+        assert_eq!(english, "sends a private message");
+
+        // unknown locales have no catalog of their own
+        let unknown = Catalog::load("xx");
+        assert!(unknown.bundle.is_none());
+        assert_eq!(unknown.format("help-private", None), english);
+        assert_eq!(unknown.format("no-such-key", None), "no-such-key");
+    }
+}
@@ -0,0 +1,27 @@
+//! Client library for the [Go-Chat-App](https://github.com/F4c3hugg3r/Go-Chat-App) server.
+//!
+//! [`Client`] is the stable entry point: it registers, sends messages, runs commands
+//! and returns everything the server sends as a stream of [`Event`]s.
+//! Own commands can be added with [`ClientBuilder::plugin`], bots which answer
+//! messages of other users are built on [`bots::bot_registry::BotHandler`].
+//!
+//! The lower level modules (`chat`, `network`, `plugins`, `service`) are public for
+//! tools which need more control, e.g. the terminal UI. They follow the protocol of
+//! the server and may change together with it.
+
+pub mod api;
+pub mod bots;
+pub mod chat;
+pub mod config;
+pub mod helper;
+pub mod i18n;
+pub mod network;
+pub mod plugins;
+pub mod service;
+pub mod types;
+
+pub use api::client::{Client, ClientBuilder, EventStream};
+pub use api::event::Event;
+pub use plugins::middleware::Middleware;
+pub use plugins::plugin_registry::{ArgKind, PluginInfo, PluginTrait};
+pub use types::{ChatError, ChatErrorWithMsg, Message, Response};
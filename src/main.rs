@@ -1,29 +1,77 @@
 mod UI;
-mod chat;
-mod helper;
-mod network;
-mod plugins;
-mod service;
-mod types;
+mod cli;
+mod headless;
 
 use crate::{
-    UI::app::App,
-    chat::chat_client::{self, ChatClient},
-    service::user_service::{self, UserService},
+    UI::{
+        app::App,
+        keymap::Keymap,
+        terminal::TerminalGuard,
+        theme::{self, DEFAULT_THEME, Theme},
+    },
+    cli::Args,
+    headless::{bot_host::BotHost, bridge::Bridge, runner::HeadlessRunner},
 };
+use rust_chat_client::{
+    chat::chat_client::ChatClient, chat::outbox::Outbox, config::settings::Settings, i18n,
+    plugins::plugin_registry::PluginConfig, service::user_service::UserService,
+};
+use std::process::ExitCode;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 #[tokio::main]
-async fn main() -> color_eyre::Result<()> {
+async fn main() -> color_eyre::Result<ExitCode> {
+    color_eyre::install()?;
+    let (args, steps) = Args::parse_with_steps();
+    // config errors are reported before the terminal is taken over
+    let settings = Settings::load()?;
+    i18n::catalog::init(settings.locale.as_deref());
+
+    // scripts are only loaded from the config dir of the user by the binary
+    let plugin_config = PluginConfig {
+        middleware: settings.middleware.clone(),
+        ..PluginConfig::from_config_dir()
+    };
+    if args.bridge {
+        return Ok(Bridge::new(args.server, plugin_config)
+            .await
+            .run(args.name)
+            .await);
+    }
+    if let Some(name) = &args.name
+        && !args.bot.is_empty()
+    {
+        return Ok(BotHost::new(args.server.clone(), &settings.middleware)
+            .await
+            .run(name, &args.bot)
+            .await);
+    }
+    if args.is_headless() {
+        let server_url = args.server.clone();
+        return Ok(HeadlessRunner::new(server_url, plugin_config)
+            .await
+            .run(args, steps)
+            .await);
+    }
+
+    let keymap = Keymap::from_settings(&settings)?;
+    theme::set(Theme::load(
+        settings.theme.as_deref().unwrap_or(DEFAULT_THEME),
+    )?);
+
     let (tx, rx) = tokio::sync::mpsc::channel(1000);
-    let server_url = String::from("http://localhost:8080");
-    let chat_client = Arc::new(ChatClient::new_client(server_url, tx).await);
-    let user_service = Arc::new(UserService::new_user_service(chat_client));
+    let chat_client = Arc::new(ChatClient::new_client(args.server, tx).await);
+    // messages which could not be sent in the last session are sent after the registration
+    if let Some(path) = Outbox::default_path() {
+        chat_client.outbox.persist_to(path)?;
+    }
+    let user_service = Arc::new(UserService::new_user_service(chat_client, plugin_config));
 
-    color_eyre::install()?;
-    let terminal = ratatui::init();
-    let result = App::new(user_service, rx).run(terminal).await;
-    ratatui::restore();
-    result
+    // the guard restores the terminal on every return and on panics
+    let (terminal, _guard) = TerminalGuard::init()?;
+    App::new(user_service, rx, keymap, &settings)
+        .run(terminal)
+        .await
+        .map(|_| ExitCode::SUCCESS)
 }
@@ -1,5 +1,9 @@
 use crate::chat::chat_client::ChatClient;
+use crate::chat::control;
+use crate::chat::receipts;
+use crate::chat::replies;
 use crate::helper::generate_secure_token;
+use crate::plugins::middleware::MiddlewareChain;
 use crate::types;
 use crate::types::ChatError;
 use crate::types::ChatErrorWithMsg;
@@ -19,6 +23,8 @@ pub struct HttpClient {
     http_client: reqwest::Client,
     pub endpoints: HashMap<types::Endpoint, String>,
     auth_token: Arc<Mutex<String>>,
+    // changes the posted messages of the plugins and the polled responses
+    pub middleware: MiddlewareChain,
 }
 
 impl HttpClient {
@@ -31,6 +37,7 @@ impl HttpClient {
             http_client: reqwest::Client::new(),
             endpoints: HashMap::new(),
             auth_token,
+            middleware: MiddlewareChain::new(),
         };
 
         client.register_endpoints(server_url, client_id).await;
@@ -141,6 +148,29 @@ impl HttpClient {
         endpoint: types::Endpoint,
         msg: Message,
     ) -> Result<types::Response, ChatErrorWithMsg> {
+        // the registration is no chat message and is not changed
+        let mut msg = if endpoint == types::Endpoint::PostPlugin {
+            match self.middleware.outgoing(msg).await? {
+                Some(msg) => msg,
+                None => return Ok(Response::empty()),
+            }
+        } else {
+            msg
+        };
+        // the server only forwards the content, so the parent and the id are added to
+        // it. The text is escaped, so it is never read as a control message.
+        if !msg.message_id.is_empty() {
+            msg.content = control::escape(&msg.content);
+            if let Some(reply_to) = &msg.reply_to {
+                msg.content = replies::attach_reply(&msg.content, reply_to);
+            }
+            msg.content = if msg.plugin == "/private" {
+                receipts::attach_id(&msg.content, &msg.message_id)
+            } else {
+                receipts::attach_group_id(&msg.content, &msg.message_id)
+            };
+        }
+
         let body = serde_json::to_string(&msg)
             .map_err(|e| ChatErrorWithMsg::new(ChatError::HttpError, e.to_string()))?;
         let endpoint_url = match self.endpoints.get(&endpoint) {
@@ -162,14 +192,28 @@ impl HttpClient {
             .body(body)
             .send()
             .await
-            .map_err(|e| ChatErrorWithMsg::new(ChatError::HttpError, e.to_string()))?;
+            .map_err(send_error)?;
 
         match http_response.error_for_status() {
             Ok(rsp) => rsp
                 .json()
                 .await
                 .map_err(|e| ChatErrorWithMsg::new(ChatError::HttpError, e.to_string())),
-            Err(e) => Err(ChatErrorWithMsg::new(ChatError::HttpError, e.to_string())),
+            Err(e) => Err(send_error(e)),
         }
     }
 }
+
+// the server could not be reached or failed, the message can be sent again later
+fn send_error(e: reqwest::Error) -> ChatErrorWithMsg {
+    let offline = e.is_connect()
+        || e.is_timeout()
+        || e.is_request()
+        || e.status().is_some_and(|s| s.is_server_error());
+    let kind = if offline {
+        ChatError::Offline
+    } else {
+        ChatError::HttpError
+    };
+    ChatErrorWithMsg::new(kind, e.to_string())
+}
@@ -0,0 +1,141 @@
+use crate::types::{ChatErrorWithMsg, Message, Response};
+use async_trait::async_trait;
+use std::sync::{Arc, RwLock};
+
+/// Middleware changes messages on their way to and from the server, e.g. to
+/// filter words or to encrypt the content.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Changes a message before it is posted, `None` drops it.
+    async fn outgoing(&self, msg: Message) -> Result<Option<Message>, ChatErrorWithMsg> {
+        Ok(Some(msg))
+    }
+
+    /// Changes a response of the server before it is shown, `None` drops it.
+    async fn incoming(&self, rsp: Response) -> Option<Response> {
+        Some(rsp)
+    }
+}
+
+struct Entry {
+    name: String,
+    middleware: Arc<dyn Middleware>,
+    enabled: bool,
+}
+
+// MiddlewareChain runs outgoing messages through the middleware in the order they
+// were added and incoming responses in the reverse order, so e.g. the last one
+// encrypts first and decrypts first
+#[derive(Default)]
+pub struct MiddlewareChain {
+    entries: RwLock<Vec<Entry>>,
+}
+
+impl std::fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.list()).finish()
+    }
+}
+
+impl MiddlewareChain {
+    pub fn new() -> MiddlewareChain {
+        MiddlewareChain::default()
+    }
+
+    // Add appends a middleware, one with the same name is replaced in place
+    pub fn add(&self, name: &str, middleware: Box<dyn Middleware>, enabled: bool) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        let entry = Entry {
+            name: name.to_string(),
+            middleware: Arc::from(middleware),
+            enabled,
+        };
+        match entries.iter_mut().find(|e| e.name == name) {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+
+    // returns false if there is no middleware with that name
+    pub fn set_enabled(&self, name: &str, enabled: bool) -> bool {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        match entries.iter_mut().find(|e| e.name == name) {
+            Some(entry) => {
+                entry.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // names in the order of the chain with their state
+    pub fn list(&self) -> Vec<(String, bool)> {
+        self.entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|e| (e.name.clone(), e.enabled))
+            .collect()
+    }
+
+    fn enabled(&self) -> Vec<Arc<dyn Middleware>> {
+        self.entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|e| e.enabled)
+            .map(|e| e.middleware.clone())
+            .collect()
+    }
+
+    pub async fn outgoing(&self, msg: Message) -> Result<Option<Message>, ChatErrorWithMsg> {
+        let mut msg = msg;
+        for middleware in self.enabled() {
+            match middleware.outgoing(msg).await? {
+                Some(changed) => msg = changed,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(msg))
+    }
+
+    pub async fn incoming(&self, rsp: Response) -> Option<Response> {
+        let mut rsp = rsp;
+        for middleware in self.enabled().iter().rev() {
+            rsp = middleware.incoming(rsp).await?;
+        }
+        Some(rsp)
+    }
+}
+
+// shortcodes which the emoji middleware replaces
+const EMOJIS: [(&str, &str); 12] = [
+    (":smile:", "😄"),
+    (":laughing:", "😆"),
+    (":wink:", "😉"),
+    (":heart:", "❤️"),
+    (":thumbsup:", "👍"),
+    (":thumbsdown:", "👎"),
+    (":tada:", "🎉"),
+    (":fire:", "🔥"),
+    (":rocket:", "🚀"),
+    (":eyes:", "👀"),
+    (":coffee:", "☕"),
+    (":check:", "✅"),
+];
+
+// EmojiMiddleware replaces shortcodes like :tada: in chat messages
+pub struct EmojiMiddleware;
+
+#[async_trait]
+impl Middleware for EmojiMiddleware {
+    async fn outgoing(&self, mut msg: Message) -> Result<Option<Message>, ChatErrorWithMsg> {
+        // only chat text, the content of commands could be a name
+        if msg.plugin == "/broadcast" || msg.plugin == "/private" {
+            for (code, emoji) in EMOJIS {
+                msg.content = msg.content.replace(code, emoji);
+            }
+        }
+        Ok(Some(msg))
+    }
+}
@@ -1,2 +1,4 @@
+pub mod middleware;
 pub mod plugin_registry;
 pub mod plugins;
+pub mod script_host;
@@ -1,10 +1,16 @@
 use crate::chat::chat_client::ChatClient;
+use crate::config::aliases::UserAliases;
+use crate::config::settings;
+use crate::i18n::catalog::{tr, tr_args};
+use crate::plugins::middleware::{EmojiMiddleware, Middleware};
 use crate::plugins::plugins;
-use crate::types::{ChatError, ChatErrorWithMsg, Message};
+use crate::plugins::script_host::ScriptHost;
+use crate::types::{ChatError, ChatErrorWithMsg, Message, Response};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use std::collections::HashMap;
 use std::error::Error;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::{Mutex, Notify};
@@ -13,6 +19,158 @@ use tokio::sync::{Mutex, Notify};
 pub trait PluginTrait: Send + Sync {
     // + Send + Sync um Errors Thread Safe zu machen
     async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg>;
+
+    /// Describes the command for the help and the argument check. Plugins without
+    /// info are listed nowhere and get the arguments unchecked.
+    fn info(&self) -> Option<PluginInfo> {
+        None
+    }
+}
+
+/// The type of a command argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgKind {
+    /// a single word
+    Word,
+    /// an integer
+    Number,
+    /// one of the given words
+    Choice(Vec<String>),
+    /// the rest of the input, only useful as last argument
+    Text,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginArg {
+    pub name: String,
+    pub kind: ArgKind,
+    pub required: bool,
+}
+
+/// Name, aliases, description and arguments of a command.
+///
+/// ```
+/// use rust_chat_client::plugins::plugin_registry::{ArgKind, PluginInfo};
+///
+/// let info = PluginInfo::new("/deploy", "Announces a deploy")
+///     .alias("/d")
+///     .arg("service", ArgKind::Word)
+///     .optional_arg("note", ArgKind::Text);
+/// assert_eq!(info.usage(), "/deploy {service} [note]");
+/// assert!(info.check("api").is_ok());
+/// assert!(info.check("").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginInfo {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+    pub args: Vec<PluginArg>,
+}
+
+impl PluginInfo {
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> PluginInfo {
+        PluginInfo {
+            name: name.into(),
+            aliases: Vec::new(),
+            description: description.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    pub fn arg(mut self, name: impl Into<String>, kind: ArgKind) -> Self {
+        self.args.push(PluginArg {
+            name: name.into(),
+            kind,
+            required: true,
+        });
+        self
+    }
+
+    pub fn optional_arg(mut self, name: impl Into<String>, kind: ArgKind) -> Self {
+        self.args.push(PluginArg {
+            name: name.into(),
+            kind,
+            required: false,
+        });
+        self
+    }
+
+    /// The command with its arguments, e.g. `/private {client_id} {text}`.
+    /// Optional arguments are shown in square brackets.
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.clone();
+        for arg in &self.args {
+            let name = match &arg.kind {
+                ArgKind::Choice(choices) => choices.join("|"),
+                _ => arg.name.clone(),
+            };
+            if arg.required {
+                usage.push_str(&format!(" {{{name}}}"));
+            } else {
+                usage.push_str(&format!(" [{name}]"));
+            }
+        }
+        usage
+    }
+
+    /// Checks the input after the command against the arguments.
+    pub fn check(&self, content: &str) -> Result<(), ChatErrorWithMsg> {
+        let mut rest = content.trim();
+        for arg in &self.args {
+            if rest.is_empty() {
+                if arg.required {
+                    return Err(
+                        self.usage_error(tr_args("arg-missing", &[("arg", arg.name.clone())]))
+                    );
+                }
+                continue;
+            }
+
+            let value = if arg.kind == ArgKind::Text {
+                std::mem::take(&mut rest)
+            } else {
+                let (value, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                rest = remainder.trim_start();
+                value
+            };
+
+            match &arg.kind {
+                ArgKind::Number if value.parse::<i64>().is_err() => {
+                    return Err(
+                        self.usage_error(tr_args("arg-number", &[("arg", arg.name.clone())]))
+                    );
+                }
+                ArgKind::Choice(choices) if !choices.iter().any(|c| c == value) => {
+                    return Err(self.usage_error(tr_args(
+                        "arg-choice",
+                        &[("arg", arg.name.clone()), ("choices", choices.join(", "))],
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(self.usage_error(tr("arg-too-many")));
+        }
+        Ok(())
+    }
+
+    fn usage_error(&self, problem: String) -> ChatErrorWithMsg {
+        ChatErrorWithMsg::new(
+            ChatError::WrongInput,
+            tr_args(
+                "plugin-usage",
+                &[("problem", problem), ("usage", self.usage())],
+            ),
+        )
+    }
 }
 
 impl Debug for dyn PluginTrait {
@@ -21,43 +179,147 @@ impl Debug for dyn PluginTrait {
     }
 }
 
+/// Which files of the user the plugins read. Nothing is read by default, so
+/// library users and tests don't depend on the config of the user.
+#[derive(Debug, Clone, Default)]
+pub struct PluginConfig {
+    /// Directory of the rhai scripts, see [`ScriptHost`].
+    pub scripts_dir: Option<PathBuf>,
+    /// File of the aliases defined with `/alias`, without one they are only kept
+    /// while the client runs.
+    pub aliases_file: Option<PathBuf>,
+    /// Middleware name -> enabled, overrides whether a middleware starts enabled.
+    pub middleware: HashMap<String, bool>,
+}
+
+impl PluginConfig {
+    /// The files in the config directory of the user, as the terminal UI reads them.
+    pub fn from_config_dir() -> PluginConfig {
+        PluginConfig {
+            scripts_dir: settings::config_dir().map(|dir| dir.join("plugins")),
+            aliases_file: UserAliases::default_path(),
+            middleware: HashMap::new(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct PluginRegistry<'a> {
     pub plugins: HashMap<&'a str, Box<dyn PluginTrait>>,
+    // help and arguments of the plugins which describe themselves
+    pub infos: HashMap<&'a str, PluginInfo>,
+    // alias -> command
+    pub aliases: HashMap<String, &'a str>,
     chat_client: Arc<ChatClient>,
     pub forward_plugins: Vec<&'a str>,
+    // plugins which can be used without being registered
+    pub local_plugins: Vec<&'a str>,
+    // commands of the user scripts
+    pub scripts: Arc<ScriptHost>,
+    // aliases and macros defined with /alias
+    pub user_aliases: Arc<UserAliases>,
+    // middleware name -> enabled from the PluginConfig
+    middleware_settings: HashMap<String, bool>,
 }
 
 impl<'a> PluginRegistry<'a> {
-    pub fn register_plugins(chat_client: Arc<ChatClient>) -> PluginRegistry<'static> {
+    pub fn register_plugins(
+        chat_client: Arc<ChatClient>,
+        config: PluginConfig,
+    ) -> PluginRegistry<'static> {
         let mut pr = PluginRegistry {
+            scripts: Arc::new(ScriptHost::new(chat_client.clone(), config.scripts_dir)),
+            user_aliases: Arc::new(match config.aliases_file {
+                Some(path) => UserAliases::with_path(path),
+                None => UserAliases::new(),
+            }),
+            middleware_settings: config.middleware,
             chat_client,
             plugins: HashMap::new(),
+            infos: HashMap::new(),
+            aliases: HashMap::new(),
             forward_plugins: Vec::new(),
+            local_plugins: Vec::new(),
         };
-        pr.plugins.insert(
+        let chat_client = pr.chat_client.clone();
+        pr.register(
             "/register",
             Box::new(plugins::RegisterClientPlugin::new_register_client_plugin(
-                pr.chat_client.clone(),
+                chat_client.clone(),
             )),
+            false,
         );
-        pr.plugins.insert(
+        pr.register(
             "/quit",
             Box::new(plugins::LogOutPlugin::new_logout_plugin(
-                pr.chat_client.clone(),
+                chat_client.clone(),
             )),
+            false,
         );
-        pr.plugins.insert(
+        pr.register(
             "/private",
             Box::new(plugins::PrivateMessagePlugin::new_private_message_plugin(
-                pr.chat_client.clone(),
+                chat_client.clone(),
             )),
+            false,
         );
-        pr.plugins.insert(
-            "",
-            Box::new(plugins::ForwardPlugin::new_forward_plugin(
-                pr.chat_client.clone(),
+        pr.register(
+            "/theme",
+            Box::new(plugins::ThemePlugin::new_theme_plugin(chat_client.clone())),
+            true,
+        );
+        pr.register(
+            "/plugins",
+            Box::new(plugins::ScriptsPlugin::new_scripts_plugin(
+                chat_client.clone(),
+                pr.scripts.clone(),
             )),
+            true,
+        );
+        pr.register(
+            "/alias",
+            Box::new(plugins::AliasPlugin::new_alias_plugin(
+                chat_client.clone(),
+                pr.user_aliases.clone(),
+                pr.scripts.clone(),
+            )),
+            true,
+        );
+        pr.register(
+            "/middleware",
+            Box::new(plugins::MiddlewarePlugin::new_middleware_plugin(
+                chat_client.clone(),
+            )),
+            true,
+        );
+        pr.register(
+            "/edit",
+            Box::new(plugins::EditPlugin::new_edit_plugin(chat_client.clone())),
+            false,
+        );
+        pr.register(
+            "/delete",
+            Box::new(plugins::DeletePlugin::new_delete_plugin(
+                chat_client.clone(),
+            )),
+            false,
+        );
+        pr.register(
+            "/react",
+            Box::new(plugins::ReactPlugin::new_react_plugin(chat_client.clone())),
+            false,
+        );
+        pr.register(
+            "/outbox",
+            Box::new(plugins::OutboxPlugin::new_outbox_plugin(
+                chat_client.clone(),
+            )),
+            true,
+        );
+        pr.register(
+            "",
+            Box::new(plugins::ForwardPlugin::new_forward_plugin(chat_client)),
+            false,
         );
         // pr.plugins.insert(
         //     "/call",
@@ -65,13 +327,79 @@ impl<'a> PluginRegistry<'a> {
         //         pr.chat_client.clone(),
         //     )),
         // );
+        pr.register_middleware("emoji", Box::new(EmojiMiddleware), false);
         pr.fill_forward_plugins();
+        pr.load_scripts();
+        if let Err(e) = pr.user_aliases.load() {
+            let _ = pr
+                .chat_client
+                .output
+                .try_send(Response::error(e.to_string()));
+        }
         pr
     }
 
-    pub async fn find_and_execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+    // loads the user scripts, errors are shown in the chat
+    fn load_scripts(&self) {
+        for command in self.forward_plugins.iter() {
+            self.scripts.reserve(command);
+            self.user_aliases.reserve(command);
+        }
+        let (_, errors) = self.scripts.load();
+        for err in errors {
+            let _ = self.chat_client.output.try_send(Response::error(err));
+        }
+    }
+
+    // Register adds a plugin for a command, an existing plugin of that command is replaced.
+    // Local plugins can be used without being registered at the server.
+    // The aliases of the plugin info are registered as well.
+    pub fn register(&mut self, command: &'a str, plugin: Box<dyn PluginTrait>, local: bool) {
+        self.scripts.reserve(command);
+        self.user_aliases.reserve(command);
+        self.infos.remove(command);
+        self.aliases.retain(|_, target| *target != command);
+        if let Some(info) = plugin.info() {
+            for alias in &info.aliases {
+                self.scripts.reserve(alias);
+                self.user_aliases.reserve(alias);
+                self.aliases.insert(alias.clone(), command);
+            }
+            self.infos.insert(command, info);
+        }
+        self.plugins.insert(command, plugin);
+        if local && !self.local_plugins.contains(&command) {
+            self.local_plugins.push(command);
+        }
+    }
+
+    // Register_middleware appends a middleware to the chain, the config can
+    // enable or disable it
+    pub fn register_middleware(&self, name: &str, middleware: Box<dyn Middleware>, enabled: bool) {
+        let enabled = self
+            .middleware_settings
+            .get(name)
+            .copied()
+            .unwrap_or(enabled);
+        self.chat_client
+            .http_client
+            .middleware
+            .add(name, middleware, enabled);
+    }
+
+    pub async fn find_and_execute(&self, mut msg: Message) -> Result<String, ChatErrorWithMsg> {
+        // plugins like /private send their command to the server, so aliases are replaced
+        if let Some(command) = self.aliases.get(&msg.plugin) {
+            msg.plugin = command.to_string();
+        }
         let command = msg.plugin.as_str();
-        if command != "/register" && !*self.chat_client.registered.lock().await {
+        // scripts decide themselves, sending fails if the client is not registered
+        let is_script = self.scripts.has(command);
+        if command != "/register"
+            && !self.local_plugins.contains(&command)
+            && !is_script
+            && !*self.chat_client.registered.lock().await
+        {
             return Err(ChatErrorWithMsg::new(
                 ChatError::NoPermission,
                 String::from("You have to be registered"),
@@ -95,12 +423,17 @@ impl<'a> PluginRegistry<'a> {
             command
         };
 
+        if let Some(info) = self.infos.get(command) {
+            info.check(&msg.content)?;
+        }
+
         let plugin = self.plugins.get(new_command);
         match plugin {
             Some(plugin) => match plugin.execute(msg).await {
                 Ok(str) => Ok(str),
                 Err(err) => Err(err),
             },
+            None if is_script => self.scripts.execute(msg).await,
             None => Err(ChatErrorWithMsg::new(
                 ChatError::EmptyField,
                 format!("Plugin field in message is empty {:?}", msg),
@@ -108,6 +441,27 @@ impl<'a> PluginRegistry<'a> {
         }
     }
 
+    // Is_command is true for all commands which are no user alias
+    pub fn is_command(&self, command: &str) -> bool {
+        self.plugins.contains_key(command)
+            || self.aliases.contains_key(command)
+            || self.forward_plugins.contains(&command)
+            || self.scripts.has(command)
+    }
+
+    // Help returns the infos of the plugins, the script commands and the aliases sorted by name
+    pub fn help(&self) -> Vec<PluginInfo> {
+        let mut infos: Vec<PluginInfo> = self
+            .infos
+            .values()
+            .cloned()
+            .chain(self.scripts.commands().iter().map(|c| c.info()))
+            .chain(self.user_aliases.infos())
+            .collect();
+        infos.sort_by(|a, b| a.name.cmp(&b.name));
+        infos
+    }
+
     pub fn fill_forward_plugins(&mut self) {
         self.forward_plugins.push("/help");
         self.forward_plugins.push("/time");
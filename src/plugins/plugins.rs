@@ -1,7 +1,18 @@
+use crate::chat::chat_service::MESSAGE_ID_LEN;
+use crate::chat::edits::{self, SentMessage};
+use crate::chat::outbox::{OutboxState, QueuedMessage};
+use crate::chat::reactions;
+use crate::chat::receipts::{DeliveryStatus, DeliveryUpdate};
+use crate::chat::replies::ReplyTo;
 use crate::chat::{self};
+use crate::config::aliases::{self, UserAliases};
 use crate::helper::generate_secure_token;
+use crate::i18n::catalog::{tr, tr_args, tr_count};
+use crate::plugins::plugin_registry::{ArgKind, PluginInfo};
+use crate::plugins::script_host::ScriptHost;
 use crate::types::{
-    self, ChatError, ChatErrorWithMsg, Endpoint, REGISTER_FLAG, Response, UNREGISTER_FLAG,
+    self, ChatError, ChatErrorWithMsg, DELIVERY_FLAG, EDIT_FLAG, Endpoint, REACTION_FLAG,
+    REGISTER_FLAG, Response, THEME_FLAG, UNREGISTER_FLAG,
 };
 use crate::{chat::chat_client::ChatClient, plugins::plugin_registry::PluginTrait, types::Message};
 use async_trait::async_trait;
@@ -22,33 +33,82 @@ impl PrivateMessagePlugin {
 
 #[async_trait]
 impl PluginTrait for PrivateMessagePlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(
+            PluginInfo::new("/private", tr("help-private"))
+                .alias("/msg")
+                .arg("client_id", ArgKind::Word)
+                .arg("text", ArgKind::Text),
+        )
+    }
+
     async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
-        if msg.content.is_empty() {
-            return Err(ChatErrorWithMsg::new(
-                ChatError::WrongInput,
-                String::from("You should supply the Id of the receiver"),
-            ));
-        }
+        // the arguments have been checked by the registry
+        let (opposing_id, content) = msg.content.split_once(' ').unwrap_or((&msg.content, ""));
+        let content = content.trim_start_matches(" ");
+        let chat_client = self.chat_client.clone();
 
-        let opposing_id = msg.content.split_whitespace().next().unwrap_or("");
+        let id = generate_secure_token(MESSAGE_ID_LEN);
+        let queued = QueuedMessage {
+            id: 0,
+            plugin: msg.plugin.clone(),
+            text: content.to_string(),
+            receiver: opposing_id.to_string(),
+            message_id: id.clone(),
+            reply_to: msg.reply_to.clone(),
+            error: None,
+        };
 
-        let replaced = msg.content.replace(opposing_id, "");
-        let content = replaced.trim_start_matches(" ");
-        let chat_client = self.chat_client.clone();
-        let message = chat_client
-            .create_message(
-                msg.name,
-                msg.plugin,
-                content.to_string(),
-                opposing_id.to_string(),
-            )
-            .await;
+        self.delivery_update(
+            opposing_id,
+            &id,
+            DeliveryStatus::Sending,
+            content,
+            msg.reply_to,
+        )
+        .await;
+        // a queued message stays in the sending state
+        if let Err(e) = chat_client.send_chat(queued).await {
+            self.delivery_update(opposing_id, &id, DeliveryStatus::Failed, "", None)
+                .await;
+            return Err(e);
+        }
+        chat_client.sent_messages.record(SentMessage {
+            id,
+            plugin: msg.plugin,
+            receiver: opposing_id.to_string(),
+            text: content.to_string(),
+        });
+        Ok(String::new())
+    }
+}
 
-        let rsp: Response = chat_client
-            .http_client
-            .post_message(Endpoint::PostPlugin, message)
-            .await?;
-        return Ok(String::new());
+impl PrivateMessagePlugin {
+    // tells the UI about the state of a sent message
+    async fn delivery_update(
+        &self,
+        client_id: &str,
+        id: &str,
+        status: DeliveryStatus,
+        text: &str,
+        reply_to: Option<ReplyTo>,
+    ) {
+        let update = DeliveryUpdate {
+            id: id.to_string(),
+            status,
+            text: text.to_string(),
+            reply_to,
+        };
+        let _ = self
+            .chat_client
+            .output
+            .send(Response {
+                client_id: client_id.to_string(),
+                rsp_name: DELIVERY_FLAG.to_string(),
+                content: serde_json::to_string(&update).unwrap_or_default(),
+                err: String::new(),
+            })
+            .await;
     }
 }
 
@@ -64,6 +124,10 @@ impl LogOutPlugin {
 
 #[async_trait]
 impl PluginTrait for LogOutPlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(PluginInfo::new("/quit", tr("help-quit")).alias("/exit"))
+    }
+
     async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
         let chat_client = self.chat_client.clone();
         chat_client.http_client.delete_request(msg).await?;
@@ -86,6 +150,10 @@ impl RegisterClientPlugin {
 
 #[async_trait]
 impl PluginTrait for RegisterClientPlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(PluginInfo::new("/register", tr("help-register")).arg("name", ArgKind::Text))
+    }
+
     async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
         if msg.content.len() > 50 || msg.content.len() < 3 {
             return Err(ChatErrorWithMsg::new(
@@ -122,6 +190,13 @@ impl ForwardPlugin {
 #[async_trait]
 impl PluginTrait for ForwardPlugin {
     async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        // chat messages get an id and are queued while the server is not reachable
+        if msg.plugin == "/broadcast" {
+            self.chat_client
+                .send_text(&msg.plugin, &msg.content, "", msg.reply_to)
+                .await?;
+            return Ok(String::new());
+        }
         let _ = self
             .chat_client
             .http_client
@@ -130,3 +205,518 @@ impl PluginTrait for ForwardPlugin {
         return Ok(String::new());
     }
 }
+
+// ThemePlugin hands the requested theme over to the UI, which owns the themes
+pub struct ThemePlugin {
+    chat_client: Arc<ChatClient>,
+}
+
+impl ThemePlugin {
+    pub fn new_theme_plugin(chat_client: Arc<ChatClient>) -> ThemePlugin {
+        ThemePlugin { chat_client }
+    }
+}
+
+#[async_trait]
+impl PluginTrait for ThemePlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(PluginInfo::new("/theme", tr("help-theme")).optional_arg("name", ArgKind::Word))
+    }
+
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        self.chat_client
+            .output
+            .send(Response {
+                client_id: String::new(),
+                rsp_name: THEME_FLAG.to_string(),
+                content: msg.content.trim().to_string(),
+                err: String::new(),
+            })
+            .await
+            .map_err(|e| ChatErrorWithMsg::new(ChatError::PluginError, e.to_string()))?;
+        Ok(String::new())
+    }
+}
+
+// ScriptsPlugin lists the commands of the user scripts and reloads them
+pub struct ScriptsPlugin {
+    chat_client: Arc<ChatClient>,
+    scripts: Arc<ScriptHost>,
+}
+
+impl ScriptsPlugin {
+    pub fn new_scripts_plugin(
+        chat_client: Arc<ChatClient>,
+        scripts: Arc<ScriptHost>,
+    ) -> ScriptsPlugin {
+        ScriptsPlugin {
+            chat_client,
+            scripts,
+        }
+    }
+
+    async fn output(&self, rsp: Response) -> Result<(), ChatErrorWithMsg> {
+        self.chat_client
+            .output
+            .send(rsp)
+            .await
+            .map_err(|e| ChatErrorWithMsg::new(ChatError::PluginError, e.to_string()))
+    }
+}
+
+#[async_trait]
+impl PluginTrait for ScriptsPlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(
+            PluginInfo::new("/plugins", tr("help-plugins")).optional_arg(
+                "action",
+                ArgKind::Choice(vec![String::from("list"), String::from("reload")]),
+            ),
+        )
+    }
+
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        match msg.content.trim() {
+            "reload" => {
+                let (count, errors) = self.scripts.load();
+                for err in errors {
+                    self.output(Response::error(err)).await?;
+                }
+                Ok(tr_count("scripts-loaded", count))
+            }
+            "" | "list" => {
+                let commands = self.scripts.commands();
+                if commands.is_empty() {
+                    return Ok(tr("scripts-none"));
+                }
+                for command in commands {
+                    self.output(Response {
+                        client_id: String::new(),
+                        rsp_name: String::new(),
+                        content: format!("{} - {}", command.command, command.help),
+                        err: String::new(),
+                    })
+                    .await?;
+                }
+                Ok(String::new())
+            }
+            _ => Err(ChatErrorWithMsg::new(
+                ChatError::WrongInput,
+                tr("scripts-usage"),
+            )),
+        }
+    }
+}
+
+// AliasPlugin defines, lists and removes the aliases of the user
+pub struct AliasPlugin {
+    chat_client: Arc<ChatClient>,
+    user_aliases: Arc<UserAliases>,
+    scripts: Arc<ScriptHost>,
+}
+
+impl AliasPlugin {
+    pub fn new_alias_plugin(
+        chat_client: Arc<ChatClient>,
+        user_aliases: Arc<UserAliases>,
+        scripts: Arc<ScriptHost>,
+    ) -> AliasPlugin {
+        AliasPlugin {
+            chat_client,
+            user_aliases,
+            scripts,
+        }
+    }
+}
+
+#[async_trait]
+impl PluginTrait for AliasPlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(
+            PluginInfo::new("/alias", tr("help-alias"))
+                .optional_arg("name = expansion", ArgKind::Text),
+        )
+    }
+
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        let content = msg.content.trim();
+        if content.is_empty() {
+            let all = self.user_aliases.all();
+            if all.is_empty() {
+                return Ok(tr("aliases-none"));
+            }
+            for (name, expansion) in all {
+                let _ = self
+                    .chat_client
+                    .output
+                    .send(Response {
+                        client_id: String::new(),
+                        rsp_name: String::new(),
+                        content: format!("{name} = {expansion}"),
+                        err: String::new(),
+                    })
+                    .await;
+            }
+            return Ok(String::new());
+        }
+
+        let Some((name, expansion)) = content.split_once('=') else {
+            return Err(ChatErrorWithMsg::new(
+                ChatError::WrongInput,
+                tr("alias-usage"),
+            ));
+        };
+        let name = name.trim();
+        let name = if name.starts_with('/') {
+            name.to_string()
+        } else {
+            format!("/{name}")
+        };
+        if !aliases::valid_name(&name) {
+            return Err(ChatErrorWithMsg::new(
+                ChatError::WrongInput,
+                tr_args("alias-invalid", &[("name", name)]),
+            ));
+        }
+        if self.user_aliases.is_reserved(&name) || self.scripts.has(&name) {
+            return Err(ChatErrorWithMsg::new(
+                ChatError::WrongInput,
+                tr_args("alias-taken", &[("name", name)]),
+            ));
+        }
+
+        // an empty expansion removes the alias
+        let expansion = expansion.trim();
+        if expansion.is_empty() {
+            let removed = self
+                .user_aliases
+                .remove(&name)
+                .map_err(|e| ChatErrorWithMsg::new(ChatError::PluginError, e.to_string()))?;
+            return match removed {
+                true => Ok(tr_args("alias-removed", &[("name", name)])),
+                false => Err(ChatErrorWithMsg::new(
+                    ChatError::NotAvailable,
+                    tr_args("alias-unknown", &[("name", name)]),
+                )),
+            };
+        }
+
+        self.user_aliases
+            .set(&name, expansion)
+            .map_err(|e| ChatErrorWithMsg::new(ChatError::PluginError, e.to_string()))?;
+        Ok(tr_args("alias-set", &[("name", name)]))
+    }
+}
+
+// MiddlewarePlugin lists the middleware and switches it on and off
+pub struct MiddlewarePlugin {
+    chat_client: Arc<ChatClient>,
+}
+
+impl MiddlewarePlugin {
+    pub fn new_middleware_plugin(chat_client: Arc<ChatClient>) -> MiddlewarePlugin {
+        MiddlewarePlugin { chat_client }
+    }
+}
+
+#[async_trait]
+impl PluginTrait for MiddlewarePlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(
+            PluginInfo::new("/middleware", tr("help-middleware"))
+                .optional_arg(
+                    "state",
+                    ArgKind::Choice(vec![String::from("on"), String::from("off")]),
+                )
+                .optional_arg("name", ArgKind::Word),
+        )
+    }
+
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        let chain = &self.chat_client.http_client.middleware;
+        let mut args = msg.content.split_whitespace();
+        let (state, name) = match (args.next(), args.next()) {
+            (None, _) => {
+                let list = chain.list();
+                if list.is_empty() {
+                    return Ok(tr("middleware-none"));
+                }
+                let list: Vec<String> = list
+                    .into_iter()
+                    .map(|(name, enabled)| {
+                        let state = if enabled { "on" } else { "off" };
+                        format!("{name} ({state})")
+                    })
+                    .collect();
+                return Ok(list.join(", "));
+            }
+            (Some(state), Some(name)) => (state == "on", name),
+            (Some(_), None) => {
+                return Err(ChatErrorWithMsg::new(
+                    ChatError::WrongInput,
+                    tr("middleware-usage"),
+                ));
+            }
+        };
+
+        if !chain.set_enabled(name, state) {
+            return Err(ChatErrorWithMsg::new(
+                ChatError::NotAvailable,
+                tr_args("middleware-unknown", &[("name", name.to_string())]),
+            ));
+        }
+        let key = if state {
+            "middleware-enabled"
+        } else {
+            "middleware-disabled"
+        };
+        Ok(tr_args(key, &[("name", name.to_string())]))
+    }
+}
+
+// OutboxPlugin lists the queued messages and cancels or resends them
+pub struct OutboxPlugin {
+    chat_client: Arc<ChatClient>,
+}
+
+impl OutboxPlugin {
+    pub fn new_outbox_plugin(chat_client: Arc<ChatClient>) -> OutboxPlugin {
+        OutboxPlugin { chat_client }
+    }
+}
+
+#[async_trait]
+impl PluginTrait for OutboxPlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(
+            PluginInfo::new("/outbox", tr("help-outbox"))
+                .optional_arg(
+                    "action",
+                    ArgKind::Choice(vec![String::from("cancel"), String::from("resend")]),
+                )
+                .optional_arg("number", ArgKind::Number),
+        )
+    }
+
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        let outbox = &self.chat_client.outbox;
+        let mut args = msg.content.split_whitespace();
+        let (action, id) = match (args.next(), args.next()) {
+            (None, _) => {
+                let messages = outbox.messages();
+                if messages.is_empty() {
+                    return Ok(tr("outbox-empty"));
+                }
+                for m in messages {
+                    let line = match m.error {
+                        Some(error) => tr_args(
+                            "outbox-failed",
+                            &[("id", m.id.to_string()), ("text", m.text), ("error", error)],
+                        ),
+                        None => tr_args(
+                            "outbox-pending",
+                            &[("id", m.id.to_string()), ("text", m.text)],
+                        ),
+                    };
+                    let _ = self
+                        .chat_client
+                        .output
+                        .send(Response {
+                            client_id: String::new(),
+                            rsp_name: String::new(),
+                            content: line,
+                            err: String::new(),
+                        })
+                        .await;
+                }
+                return Ok(String::new());
+            }
+            (Some(action), Some(id)) => (action, id.parse::<u64>().unwrap_or_default()),
+            (Some(_), None) => {
+                return Err(ChatErrorWithMsg::new(
+                    ChatError::WrongInput,
+                    tr("outbox-usage"),
+                ));
+            }
+        };
+
+        let unknown = || {
+            ChatErrorWithMsg::new(
+                ChatError::NotAvailable,
+                tr_args("outbox-unknown", &[("id", id.to_string())]),
+            )
+        };
+        if action == "cancel" {
+            let cancelled = outbox.remove(id).ok_or_else(unknown)?;
+            self.chat_client
+                .outbox_update(OutboxState::Cancelled, cancelled)
+                .await;
+            return Ok(tr_args("outbox-cancelled", &[("id", id.to_string())]));
+        }
+        let resent = outbox.resend(id).ok_or_else(unknown)?;
+        self.chat_client
+            .outbox_update(OutboxState::Queued, resent)
+            .await;
+        Ok(tr_args("outbox-resent", &[("id", id.to_string())]))
+    }
+}
+
+// EditPlugin replaces the text of the last own message
+pub struct EditPlugin {
+    chat_client: Arc<ChatClient>,
+}
+
+impl EditPlugin {
+    pub fn new_edit_plugin(chat_client: Arc<ChatClient>) -> EditPlugin {
+        EditPlugin { chat_client }
+    }
+}
+
+#[async_trait]
+impl PluginTrait for EditPlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(PluginInfo::new("/edit", tr("help-edit")).arg("text", ArgKind::Text))
+    }
+
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        let sent = last_sent(&self.chat_client)?;
+        let text = msg.content.trim();
+        let chat_client = &self.chat_client;
+
+        // a message which waits in the outbox is changed before it is sent
+        if let Some(queued) = chat_client.outbox.find_message(&sent.id)
+            && let Some(changed) = chat_client.outbox.set_text(queued.id, text)
+        {
+            chat_client.sent_messages.set_text(&sent.id, text);
+            chat_client
+                .outbox_update(OutboxState::Queued, changed)
+                .await;
+            return Ok(String::new());
+        }
+
+        send_edit(chat_client, &sent, edits::edit(&sent.id, text)).await?;
+        chat_client.sent_messages.set_text(&sent.id, text);
+        Ok(String::new())
+    }
+}
+
+// DeletePlugin deletes the last own message
+pub struct DeletePlugin {
+    chat_client: Arc<ChatClient>,
+}
+
+impl DeletePlugin {
+    pub fn new_delete_plugin(chat_client: Arc<ChatClient>) -> DeletePlugin {
+        DeletePlugin { chat_client }
+    }
+}
+
+#[async_trait]
+impl PluginTrait for DeletePlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(PluginInfo::new("/delete", tr("help-delete")))
+    }
+
+    async fn execute(&self, _msg: Message) -> Result<String, ChatErrorWithMsg> {
+        let sent = last_sent(&self.chat_client)?;
+        let chat_client = &self.chat_client;
+
+        // a message which waits in the outbox is just not sent
+        if let Some(queued) = chat_client.outbox.find_message(&sent.id)
+            && let Some(cancelled) = chat_client.outbox.remove(queued.id)
+        {
+            chat_client.sent_messages.remove(&sent.id);
+            chat_client
+                .outbox_update(OutboxState::Cancelled, cancelled)
+                .await;
+            return Ok(String::new());
+        }
+
+        send_edit(chat_client, &sent, edits::delete(&sent.id)).await?;
+        chat_client.sent_messages.remove(&sent.id);
+        Ok(String::new())
+    }
+}
+
+// ReactPlugin adds a reaction to a message or takes it back. Reactions to private
+// messages need the client id of the other client, they are only sent to it.
+pub struct ReactPlugin {
+    chat_client: Arc<ChatClient>,
+}
+
+impl ReactPlugin {
+    pub fn new_react_plugin(chat_client: Arc<ChatClient>) -> ReactPlugin {
+        ReactPlugin { chat_client }
+    }
+}
+
+#[async_trait]
+impl PluginTrait for ReactPlugin {
+    fn info(&self) -> Option<PluginInfo> {
+        Some(
+            PluginInfo::new("/react", tr("help-react"))
+                .arg("message_id", ArgKind::Word)
+                .arg("emoji", ArgKind::Word)
+                .optional_arg("client_id", ArgKind::Word),
+        )
+    }
+
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        // the arguments have been checked by the registry
+        let mut args = msg.content.split_whitespace();
+        let id = args.next().unwrap_or_default();
+        let emoji = args.next().unwrap_or_default();
+        let receiver = args.next().unwrap_or_default();
+        let chat_client = &self.chat_client;
+
+        let added = chat_client.own_reactions.toggle(id, emoji);
+        let content = reactions::reaction(id, emoji, added);
+        if let Err(e) = chat_client.send_control(&content, receiver).await {
+            chat_client.own_reactions.toggle(id, emoji);
+            return Err(e);
+        }
+
+        // the server sends group messages back, own private reactions are counted through the flag
+        if !receiver.is_empty() {
+            let _ = chat_client
+                .output
+                .send(Response {
+                    client_id: chat_client.client_id.lock().await.clone(),
+                    rsp_name: REACTION_FLAG.to_string(),
+                    content,
+                    err: String::new(),
+                })
+                .await;
+        }
+        Ok(String::new())
+    }
+}
+
+fn last_sent(chat_client: &ChatClient) -> Result<SentMessage, ChatErrorWithMsg> {
+    chat_client
+        .sent_messages
+        .last()
+        .ok_or_else(|| ChatErrorWithMsg::new(ChatError::NotAvailable, tr("edit-nothing")))
+}
+
+// sends an edit to the receivers of the message. The server sends group messages
+// back, own private messages are changed through the EDIT_FLAG.
+async fn send_edit(
+    chat_client: &ChatClient,
+    sent: &SentMessage,
+    content: String,
+) -> Result<(), ChatErrorWithMsg> {
+    chat_client.send_control(&content, &sent.receiver).await?;
+    if !sent.receiver.is_empty() {
+        let _ = chat_client
+            .output
+            .send(Response {
+                client_id: sent.receiver.clone(),
+                rsp_name: EDIT_FLAG.to_string(),
+                content,
+                err: String::new(),
+            })
+            .await;
+    }
+    Ok(())
+}
@@ -0,0 +1,338 @@
+use crate::chat::chat_client::ChatClient;
+use crate::i18n::catalog::tr_args;
+use crate::plugins::plugin_registry::{ArgKind, PluginInfo};
+use crate::types::{ChatError, ChatErrorWithMsg, JsonClient, Message, Response};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{AST, Array, CallFnOptions, Dynamic, Engine, Map, Scope};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+const SCRIPT_EXTENSION: &str = "rhai";
+// called if a script registers a command without naming a handler
+const DEFAULT_HANDLER: &str = "run";
+
+// limits which stop broken or endless scripts
+const MAX_OPERATIONS: u64 = 1_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING_SIZE: usize = 64 * 1024;
+const MAX_ARRAY_SIZE: usize = 10_000;
+
+// ScriptCommand is a slash-command registered by a script
+#[derive(Debug, Clone)]
+pub struct ScriptCommand {
+    pub command: String,
+    pub help: String,
+    pub file: PathBuf,
+    handler: String,
+    ast: Arc<AST>,
+}
+
+impl ScriptCommand {
+    // scripts get the arguments unchecked
+    pub fn info(&self) -> PluginInfo {
+        PluginInfo::new(self.command.clone(), self.help.clone()).optional_arg("args", ArgKind::Text)
+    }
+}
+
+// the scripts have no direct access to the client, they queue actions
+// which are executed after the script has finished
+#[derive(Debug, Clone, PartialEq)]
+enum ScriptAction {
+    Send(String),
+    SendPrivate { client_id: String, text: String },
+    Print(String),
+}
+
+#[derive(Debug, Default)]
+struct RunState {
+    actions: Vec<ScriptAction>,
+    // (command, help, handler) registered while loading
+    registered: Vec<(String, String, String)>,
+    users: Vec<JsonClient>,
+}
+
+// ScriptHost loads the rhai scripts of the plugins directory and runs their commands.
+// A script registers its commands when it is loaded:
+//
+//     register("/deploy", "Announces a deploy: /deploy {service}");
+//     fn run(args) { send("deploying " + args); }
+#[derive(Debug)]
+pub struct ScriptHost {
+    chat_client: Arc<ChatClient>,
+    dir: Option<PathBuf>,
+    commands: RwLock<HashMap<String, ScriptCommand>>,
+    // commands of the registry which scripts must not replace
+    reserved: RwLock<Vec<String>>,
+}
+
+impl ScriptHost {
+    // without a directory no scripts are loaded
+    pub fn new(chat_client: Arc<ChatClient>, dir: Option<PathBuf>) -> ScriptHost {
+        ScriptHost {
+            chat_client,
+            dir,
+            commands: RwLock::new(HashMap::new()),
+            reserved: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn reserve(&self, command: &str) {
+        self.reserved
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(command.to_string());
+    }
+
+    // Load (re)reads all scripts, commands of broken scripts are skipped.
+    // Returns the number of commands and the errors.
+    pub fn load(&self) -> (usize, Vec<String>) {
+        let reserved = self
+            .reserved
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let mut commands = HashMap::new();
+        let mut errors = Vec::new();
+
+        for path in self.script_files() {
+            match load_script(&path) {
+                Ok(script_commands) => {
+                    for command in script_commands {
+                        if reserved.contains(&command.command)
+                            || commands.contains_key(&command.command)
+                        {
+                            errors.push(tr_args(
+                                "script-command-taken",
+                                &[
+                                    ("command", command.command.clone()),
+                                    ("file", path.display().to_string()),
+                                ],
+                            ));
+                            continue;
+                        }
+                        commands.insert(command.command.clone(), command);
+                    }
+                }
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+
+        let count = commands.len();
+        *self.commands.write().unwrap_or_else(|e| e.into_inner()) = commands;
+        (count, errors)
+    }
+
+    fn script_files(&self) -> Vec<PathBuf> {
+        let Some(Ok(entries)) = self.dir.as_ref().map(std::fs::read_dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION))
+            .collect();
+        files.sort();
+        files
+    }
+
+    pub fn has(&self, command: &str) -> bool {
+        self.commands
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(command)
+    }
+
+    // all script commands sorted by name
+    pub fn commands(&self) -> Vec<ScriptCommand> {
+        let mut commands: Vec<ScriptCommand> = self
+            .commands
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        commands.sort_by(|a, b| a.command.cmp(&b.command));
+        commands
+    }
+
+    // Execute runs the handler of a script command and afterwards its queued actions
+    pub async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        let Some(command) = self
+            .commands
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&msg.plugin)
+            .cloned()
+        else {
+            return Err(ChatErrorWithMsg::new(
+                ChatError::NotAvailable,
+                format!("Unknown command {}", msg.plugin),
+            ));
+        };
+
+        let state = Arc::new(Mutex::new(RunState {
+            users: self.chat_client.users.lock().await.clone(),
+            ..RunState::default()
+        }));
+        let engine = engine(state.clone());
+        let result = engine
+            .call_fn_with_options::<Dynamic>(
+                CallFnOptions::new().eval_ast(false),
+                &mut Scope::new(),
+                &command.ast,
+                &command.handler,
+                (msg.content.clone(),),
+            )
+            .map_err(|e| {
+                ChatErrorWithMsg::new(
+                    ChatError::PluginError,
+                    format!("{}: {}", command.file.display(), e),
+                )
+            })?;
+
+        let actions = std::mem::take(&mut state.lock().unwrap_or_else(|e| e.into_inner()).actions);
+        for action in actions {
+            self.perform(action).await?;
+        }
+
+        if result.is_unit() {
+            Ok(String::new())
+        } else {
+            Ok(result.to_string())
+        }
+    }
+
+    async fn perform(&self, action: ScriptAction) -> Result<(), ChatErrorWithMsg> {
+        let (plugin, text, client_id) = match action {
+            ScriptAction::Print(text) => {
+                let _ = self
+                    .chat_client
+                    .output
+                    .send(Response {
+                        client_id: String::new(),
+                        rsp_name: String::new(),
+                        content: text,
+                        err: String::new(),
+                    })
+                    .await;
+                return Ok(());
+            }
+            ScriptAction::Send(text) => ("/broadcast", text, String::new()),
+            ScriptAction::SendPrivate { client_id, text } => ("/private", text, client_id),
+        };
+
+        if !*self.chat_client.registered.lock().await {
+            return Err(ChatErrorWithMsg::new(
+                ChatError::NoPermission,
+                String::from("You have to be registered"),
+            ));
+        }
+        // like typed messages they get an id and are queued while the server is not reachable
+        self.chat_client
+            .send_text(plugin, &text, &client_id, None)
+            .await
+    }
+}
+
+// compiles a script and runs its top level, which registers the commands
+fn load_script(path: &Path) -> Result<Vec<ScriptCommand>, String> {
+    let state = Arc::new(Mutex::new(RunState::default()));
+    let engine = engine(state.clone());
+    let ast = engine
+        .compile_file(path.to_path_buf())
+        .map_err(|e| e.to_string())?;
+    engine.run_ast(&ast).map_err(|e| e.to_string())?;
+
+    let ast = Arc::new(ast);
+    let registered =
+        std::mem::take(&mut state.lock().unwrap_or_else(|e| e.into_inner()).registered);
+    let mut commands = Vec::new();
+    for (command, help, handler) in registered {
+        if !command.starts_with('/') || command.contains(char::is_whitespace) {
+            return Err(format!("Invalid command '{command}'"));
+        }
+        if !ast.iter_functions().any(|f| f.name == handler) {
+            return Err(format!("Missing function '{handler}' for {command}"));
+        }
+        commands.push(ScriptCommand {
+            command,
+            help,
+            file: path.to_path_buf(),
+            handler,
+            ast: ast.clone(),
+        });
+    }
+    Ok(commands)
+}
+
+// builds a sandboxed engine, scripts can only use the functions registered here
+fn engine(state: Arc<Mutex<RunState>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_ARRAY_SIZE);
+    engine.disable_symbol("eval");
+    // import would read other script files
+    engine.set_module_resolver(DummyModuleResolver::new());
+
+    let s = state.clone();
+    engine.register_fn("register", move |command: &str, help: &str| {
+        lock(&s)
+            .registered
+            .push((command.into(), help.into(), DEFAULT_HANDLER.into()));
+    });
+    let s = state.clone();
+    engine.register_fn(
+        "register",
+        move |command: &str, help: &str, handler: &str| {
+            lock(&s)
+                .registered
+                .push((command.into(), help.into(), handler.into()));
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("send", move |text: &str| {
+        lock(&s).actions.push(ScriptAction::Send(text.into()));
+    });
+    let s = state.clone();
+    engine.register_fn("send_private", move |client_id: &str, text: &str| {
+        lock(&s).actions.push(ScriptAction::SendPrivate {
+            client_id: client_id.into(),
+            text: text.into(),
+        });
+    });
+
+    // the user list of the last /users answer
+    let s = state.clone();
+    engine.register_fn("users", move || -> Array {
+        lock(&s)
+            .users
+            .iter()
+            .map(|user| {
+                let mut map = Map::new();
+                map.insert("name".into(), user.name.clone().into());
+                map.insert("client_id".into(), user.client_id.clone().into());
+                map.insert("group".into(), user.group_name.clone().into());
+                Dynamic::from_map(map)
+            })
+            .collect()
+    });
+
+    // print and debug are shown in the chat
+    let s = state.clone();
+    engine.on_print(move |text| {
+        lock(&s).actions.push(ScriptAction::Print(text.into()));
+    });
+    engine.on_debug(move |text, _, _| {
+        lock(&state).actions.push(ScriptAction::Print(text.into()));
+    });
+
+    engine
+}
+
+fn lock(state: &Mutex<RunState>) -> std::sync::MutexGuard<'_, RunState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
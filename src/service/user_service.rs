@@ -1,7 +1,8 @@
 use crate::chat::chat_client::{self, ChatClient};
+use crate::chat::replies::ReplyTo;
 use crate::helper;
-use crate::plugins::plugin_registry::{self, PluginRegistry};
-use crate::types::{Message, Response};
+use crate::plugins::plugin_registry::{self, PluginConfig, PluginRegistry};
+use crate::types::{ChatErrorWithMsg, Message, Response};
 use std::fmt::format;
 use std::sync::Arc;
 use tokio::sync::{Mutex, Notify};
@@ -13,31 +14,74 @@ pub struct UserService {
 }
 
 impl UserService {
-    pub fn new_user_service(chat_client: Arc<ChatClient>) -> UserService {
+    pub fn new_user_service(chat_client: Arc<ChatClient>, config: PluginConfig) -> UserService {
         UserService {
-            plugin_registry: Arc::new(PluginRegistry::register_plugins(chat_client.clone())),
+            plugin_registry: Arc::new(PluginRegistry::register_plugins(
+                chat_client.clone(),
+                config,
+            )),
             chat_client,
         }
     }
 
+    // used if further plugins have been added to the registry
+    pub fn with_registry(
+        chat_client: Arc<ChatClient>,
+        plugin_registry: PluginRegistry<'static>,
+    ) -> UserService {
+        UserService {
+            plugin_registry: Arc::new(plugin_registry),
+            chat_client,
+        }
+    }
+
+    // Execute parses the input and runs the matching plugin, the result is returned
+    // to the caller instead of being sent to the output channel.
+    // The steps of an alias are run one after another, their results are sent to the
+    // output channel and the first failing step stops the alias.
+    pub async fn execute(&self, input: &str) -> Result<String, ChatErrorWithMsg> {
+        let msg = self.chat_client.parse_input_to_message(input).await;
+        if self.plugin_registry.is_command(&msg.plugin) {
+            return self.plugin_registry.find_and_execute(msg).await;
+        }
+        let Some(steps) = self
+            .plugin_registry
+            .user_aliases
+            .expand(&msg.plugin, &msg.content)
+        else {
+            return self.plugin_registry.find_and_execute(msg).await;
+        };
+
+        // aliases in the steps are not expanded, so aliases can't call each other endlessly
+        for step in steps? {
+            let msg = self.chat_client.parse_input_to_message(&step).await;
+            let result = self.plugin_registry.find_and_execute(msg).await?;
+            self.send_result(Ok(result)).await;
+        }
+        Ok(String::new())
+    }
+
+    // Reply sends the input as answer to another message, only chat messages carry the parent
+    pub async fn reply(&self, input: &str, reply_to: ReplyTo) -> Result<String, ChatErrorWithMsg> {
+        let mut msg = self.chat_client.parse_input_to_message(input).await;
+        msg.reply_to = Some(reply_to);
+        self.plugin_registry.find_and_execute(msg).await
+    }
+
     pub async fn executor(&self, input: &str) {
-        let chat_client = self.chat_client.clone();
-        let msg = chat_client.parse_input_to_message(input).await;
-        // // debug //
-        // let msg_clone = msg.clone();
-        // let _ = chat_client
-        //     .output
-        //     .send(Response {
-        //         client_id: msg_clone.client_id,
-        //         rsp_name: msg_clone.name,
-        //         content: msg_clone.content,
-        //         err: msg_clone.plugin,
-        //     })
-        //     .await;
-        // // debug //
+        let result = self.execute(input).await;
+        self.send_result(result).await;
+    }
+
+    pub async fn reply_executor(&self, input: &str, reply_to: ReplyTo) {
+        let result = self.reply(input, reply_to).await;
+        self.send_result(result).await;
+    }
+
+    async fn send_result(&self, result: Result<String, ChatErrorWithMsg>) {
         let mut err = String::new();
 
-        let comment = match self.plugin_registry.find_and_execute(msg).await {
+        let comment = match result {
             Ok(rsp) => rsp,
             Err(e) => {
                 err = format!("{}: {}", e.kind, e.msg);
@@ -46,7 +90,8 @@ impl UserService {
         };
 
         // TODO log channel
-        let _ = chat_client
+        let _ = self
+            .chat_client
             .output
             .send(Response {
                 client_id: String::new(),
@@ -1,11 +1,12 @@
-use ratatui::style::palette::tailwind;
-use ratatui::style::{Color, Style};
-use ratatui::text::Span;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::chat::replies::ReplyTo;
+use crate::i18n::catalog::tr;
+
 // routes
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Endpoint {
     PostPlugin,
     PostRegister,
@@ -28,6 +29,7 @@ impl ChatErrorWithMsg {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ChatError {
     #[error("A message field is empty")]
     EmptyField,
@@ -43,6 +45,8 @@ pub enum ChatError {
     PluginError,
     #[error("An http error accured")]
     HttpError,
+    #[error("The server is not reachable")]
+    Offline,
 }
 
 // #[derive(Error, Debug)]
@@ -52,14 +56,34 @@ pub enum ChatError {
 // }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum HttpClientError {
     #[error("This endpoint is invalid")]
     InvalidEndpoint,
 }
 
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    #[error("Config file could not be read: {0}")]
+    Io(String),
+    #[error("Config file could not be parsed: {0}")]
+    Parse(String),
+    #[error("Config file could not be written: {0}")]
+    Write(String),
+    #[error("Unknown key '{0}'")]
+    UnknownKey(String),
+    #[error("Unknown action '{0}'")]
+    UnknownAction(String),
+    #[error("Unknown keymap scope '{0}'")]
+    UnknownScope(String),
+    #[error("Conflicting key bindings:\n{0}")]
+    KeyConflict(String),
+}
+
 pub fn dummy_json_client() -> JsonClient {
     JsonClient {
-        name: "Bitte registriere dich, um die Clients zu sehen.".to_string(),
+        name: tr("register-first"),
         call_state: "".to_string(),
         client_id: "".to_string(),
         group_name: "".to_string(),
@@ -67,26 +91,11 @@ pub fn dummy_json_client() -> JsonClient {
     }
 }
 
-// Farbkonstanten
-pub const RED_COLOR: Color = Color::Rgb(191, 53, 53);
-pub const BLUE_COLOR: Color = tailwind::BLUE.c400;
-pub const PURPLE_COLOR: Color = tailwind::PURPLE.c950;
-pub const TURKIS_COLOR: Color = Color::Rgb(53, 191, 188);
-pub const DARK_TURKIS_COLOR: Color = tailwind::CYAN.c800;
-pub const GREEN_COLOR: Color = Color::Rgb(62, 138, 41);
-pub const DARK_YELLOW_COLOR: Color = tailwind::YELLOW.c950;
-
-pub const DEFAULT_TITLE: &str = "Willkommen im Chatraum!";
-pub const DEFAULT_MESSAGE: &str = "-> Schreibe '/register {name}' um dich zu registrieren";
-pub const WINDOW_RESIZE_FLAG: &str = "windowResize";
-pub const REGISTER_OUTPUT: &str = "-> Du kannst nun Nachrichten schreiben oder Commands ausführen";
-pub const REGISTER_HELP_OUTPUT: &str = "[ '/help' → Befehle anzeigen ]";
-pub const REGISTER_QUIT_OUTPUT: &str = "[ '/quit' → Chat verlassen ]";
-
 // muteable device
 pub const MICROPHONE: &str = "Mic";
 pub const SPEAKER: &str = "Speaker";
 
+// the flags are only compared, the displayed texts come from the locale catalog
 pub const UNREGISTER_FLAG: &str = "- Du bist nun vom Server getrennt -";
 pub const REGISTER_FLAG: &str = "- Du bist registriert -";
 pub const ADD_GROUP_FLAG: &str = "Add Group";
@@ -97,6 +106,17 @@ pub const USERS_FLAG: &str = "Users";
 pub const IGNORE_RESPONSE_TAG: &str = "Ignore Response";
 pub const USER_ADD_FLAG: &str = "Add User";
 pub const USER_REMOVE_FLAG: &str = "Remove User";
+pub const THEME_FLAG: &str = "Theme";
+// content of a typing notification, a control message to the receiver or the group
+pub const TYPING_FLAG: &str = "\u{2063}![typing]";
+// state changes of own private messages
+pub const DELIVERY_FLAG: &str = "Delivery";
+// state changes of queued chat messages
+pub const OUTBOX_FLAG: &str = "Outbox";
+// edit or deletion of an own private message, the receiver gets the same content
+pub const EDIT_FLAG: &str = "Edit";
+// own reaction to a private message, the receiver gets the same content
+pub const REACTION_FLAG: &str = "Reaction";
 
 // signal flags
 pub const ICE_CANDIDATE_FLAG: &str = "ICE Candidate";
@@ -128,6 +148,16 @@ pub struct Message {
     pub client_id: String,
     #[serde(rename = "groupId")]
     pub group_id: String,
+    // generated for private messages, so receipts can refer to them
+    #[serde(
+        rename = "messageId",
+        default,
+        skip_serializing_if = "String::is_empty"
+    )]
+    pub message_id: String,
+    // the message this one answers, it is put into the content when it is sent
+    #[serde(rename = "replyTo", default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<ReplyTo>,
 }
 
 // Response contains the name and id of the sender, the response (content) itself
@@ -191,6 +221,24 @@ pub struct JsonClient {
     pub group_id: String,
 }
 
+impl JsonClient {
+    pub const fn ref_array(&self) -> [&String; 3] {
+        [&self.name, &self.call_state, &self.group_name]
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn call_state(&self) -> &str {
+        &self.call_state
+    }
+
+    pub fn group_name(&self) -> &str {
+        &self.group_name
+    }
+}
+
 // // signals, that a state of a client or the list of clients has changed
 // #[derive(Debug, Clone)]
 // pub struct ClientsChangeSignal {
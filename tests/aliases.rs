@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use rust_chat_client::config::aliases::{self, UserAliases};
+use rust_chat_client::{ChatErrorWithMsg, Client, Message, PluginTrait};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn steps_are_split_at_semicolons() {
+    assert_eq!(
+        aliases::split_steps("/group join $1; hello $*"),
+        vec!["/group join $1", " hello $*"]
+    );
+    assert_eq!(aliases::split_steps("hello"), vec!["hello"]);
+}
+
+#[test]
+fn escaped_semicolons_stay_in_the_step() {
+    assert_eq!(
+        aliases::split_steps(r"see you later\; bye; /quit"),
+        vec!["see you later; bye", " /quit"]
+    );
+    // other backslashes are kept
+    assert_eq!(aliases::split_steps(r"a\b"), vec![r"a\b"]);
+}
+
+fn expand(aliases: &UserAliases, name: &str, args: &str) -> Vec<String> {
+    aliases.expand(name, args).unwrap().unwrap()
+}
+
+#[test]
+fn parameters_are_replaced_by_the_arguments() {
+    let aliases = UserAliases::new();
+    aliases.set("/hi", "/group join $1; hello $2, $*").unwrap();
+    assert_eq!(
+        expand(&aliases, "/hi", "ops bob how are you"),
+        vec!["/group join ops", "hello bob, ops bob how are you"]
+    );
+
+    aliases.set("/nine", "$9 $1").unwrap();
+    assert_eq!(expand(&aliases, "/nine", "1 2 3 4 5 6 7 8 9"), vec!["9 1"]);
+    assert!(aliases.expand("/unknown", "").is_none());
+}
+
+#[test]
+fn the_number_of_arguments_is_checked() {
+    let aliases = UserAliases::new();
+    aliases.set("/two", "$1 and $2").unwrap();
+    assert!(aliases.expand("/two", "one").unwrap().is_err());
+    assert!(aliases.expand("/two", "one two three").unwrap().is_err());
+    // $* takes any number of arguments
+    aliases.set("/all", "hello $*").unwrap();
+    assert_eq!(expand(&aliases, "/all", ""), vec!["hello"]);
+    assert_eq!(expand(&aliases, "/all", "a b c"), vec!["hello a b c"]);
+}
+
+#[test]
+fn aliases_are_saved_to_the_given_file() {
+    let path = std::env::temp_dir().join(format!("chat-aliases-{}.toml", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    UserAliases::with_path(path.clone())
+        .set("/hi", "hello $1")
+        .unwrap();
+
+    let loaded = UserAliases::with_path(path.clone());
+    loaded.load().unwrap();
+    assert_eq!(expand(&loaded, "/hi", "bob"), vec!["hello bob"]);
+    let _ = std::fs::remove_file(path);
+}
+
+// records the inputs it is called with
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl PluginTrait for Recorder {
+    async fn execute(&self, msg: Message) -> Result<String, ChatErrorWithMsg> {
+        self.0.lock().unwrap().push(msg.content.trim().to_string());
+        Ok(String::new())
+    }
+}
+
+#[tokio::test]
+async fn aliases_are_not_expanded_inside_aliases() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let client = Client::builder("http://127.0.0.1:9")
+        .local_plugin("/rec", Recorder(calls.clone()))
+        .build()
+        .await;
+    client.execute("/alias inner = /rec inner").await.unwrap();
+    client
+        .execute("/alias outer = /rec outer; /inner")
+        .await
+        .unwrap();
+
+    client.execute("/inner").await.unwrap();
+    // the first step runs, /inner is no command and stops the alias
+    assert!(client.execute("/outer").await.is_err());
+    assert_eq!(*calls.lock().unwrap(), vec!["inner", "outer"]);
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use async_trait::async_trait;
+use common::MockServer;
+use rust_chat_client::Client;
+use rust_chat_client::bots::bot_registry::{
+    BotContext, BotHandler, BotRegistry, IncomingMessage, Reply, Trigger,
+};
+use rust_chat_client::bots::example_bots::{DiceBot, EchoBot, ReminderBot};
+use rust_chat_client::chat::receipts;
+use rust_chat_client::types::ChatErrorWithMsg;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// starts a mock server and a registered client which runs the bots
+async fn start(bots: Vec<Box<dyn BotHandler>>) -> (MockServer, Client) {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("testbot").await.unwrap();
+
+    let mut registry = BotRegistry::new(client.user_service().chat_client.clone());
+    for bot in bots {
+        registry.add(bot);
+    }
+    let events = client.events().unwrap();
+    tokio::spawn(async move { registry.run(events).await });
+
+    (server, client)
+}
+
+#[tokio::test]
+async fn echo_bot_answers_publicly() {
+    let (server, _client) = start(vec![Box::new(EchoBot)]).await;
+
+    server
+        .push_message("alice-id", "alice", "!echo hello there")
+        .await;
+
+    let reply = server.wait_for_post("/broadcast", TIMEOUT).await.unwrap();
+    // replies get an id like every chat message, so they can be answered
+    let (text, id) = receipts::split_id(&reply.content);
+    assert_eq!(text, "hello there");
+    assert!(id.is_some());
+    assert_eq!(reply.name, "testbot");
+}
+
+#[tokio::test]
+async fn bots_ignore_own_and_unmatched_messages() {
+    let (server, client) = start(vec![Box::new(EchoBot)]).await;
+    let own_id = client.client_id().await;
+
+    server.push_message(&own_id, "testbot", "!echo loop").await;
+    server
+        .push_message("alice-id", "alice", "!echoes are not commands")
+        .await;
+    server
+        .push_message("alice-id", "alice", "just chatting")
+        .await;
+    server.push_message("alice-id", "alice", "!echo done").await;
+
+    let replies = server
+        .wait_for_posts("/broadcast", 1, TIMEOUT)
+        .await
+        .expect("the bot did not answer in time");
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let replies_later = server
+        .wait_for_posts("/broadcast", 1, TIMEOUT)
+        .await
+        .expect("the bot did not answer in time");
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies_later.len(), 1);
+    assert_eq!(receipts::split_id(&replies[0].content).0, "done");
+}
+
+#[tokio::test]
+async fn dice_bot_rolls_in_range() {
+    let (server, _client) = start(vec![Box::new(DiceBot)]).await;
+
+    server.push_message("alice-id", "alice", "!dice 3d6").await;
+
+    let reply = server.wait_for_post("/broadcast", TIMEOUT).await.unwrap();
+    assert!(reply.content.contains("3d6"), "{}", reply.content);
+    let rolls: Vec<u32> = reply
+        .content
+        .split(": ")
+        .nth(1)
+        .unwrap()
+        .split(" (")
+        .next()
+        .unwrap()
+        .split(", ")
+        .map(|r| r.parse().unwrap())
+        .collect();
+    assert_eq!(rolls.len(), 3);
+    assert!(rolls.iter().all(|r| (1..=6).contains(r)));
+}
+
+#[tokio::test]
+async fn wrong_input_is_answered_privately() {
+    let (server, _client) = start(vec![Box::new(DiceBot)]).await;
+
+    server
+        .push_message("alice-id", "alice", "!dice 500d6")
+        .await;
+
+    let reply = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    assert_eq!(reply.client_id, "alice-id");
+    assert!(
+        server
+            .posted()
+            .await
+            .iter()
+            .all(|m| m.plugin != "/broadcast")
+    );
+}
+
+#[tokio::test]
+async fn reminder_bot_answers_later() {
+    let (server, _client) = start(vec![Box::new(ReminderBot)]).await;
+
+    server
+        .push_message("alice-id", "alice", "!remind 1s tea")
+        .await;
+
+    let confirmation = server
+        .wait_for_posts("/private", 1, TIMEOUT)
+        .await
+        .expect("the bot did not answer in time");
+    assert_eq!(confirmation.len(), 1);
+    let replies = server
+        .wait_for_posts("/private", 2, TIMEOUT)
+        .await
+        .expect("the bot did not answer in time");
+    assert!(replies[1].content.contains("tea"));
+    assert_eq!(replies[1].client_id, "alice-id");
+}
+
+struct GreetBot;
+
+#[async_trait]
+impl BotHandler for GreetBot {
+    fn name(&self) -> &str {
+        "greet"
+    }
+
+    fn trigger(&self) -> Trigger {
+        Trigger::Mention
+    }
+
+    async fn handle(
+        &self,
+        _ctx: &BotContext,
+        msg: IncomingMessage,
+    ) -> Result<Option<Reply>, ChatErrorWithMsg> {
+        Ok(Some(Reply::Private(format!(
+            "hi {}, {}",
+            msg.name, msg.args
+        ))))
+    }
+}
+
+#[tokio::test]
+async fn mentions_trigger_custom_bots() {
+    let (server, _client) = start(vec![Box::new(GreetBot)]).await;
+
+    server.push_message("bob-id", "bob", "ping @someone").await;
+    // another user whose name starts with the name of the bot
+    server
+        .push_message("bob-id", "bob", "@testbotty ping")
+        .await;
+    server
+        .push_message("bob-id", "bob", "@testbot are you there?")
+        .await;
+
+    let reply = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    assert_eq!(
+        receipts::split_id(&reply.content).0,
+        "hi bob, are you there?"
+    );
+    assert_eq!(reply.client_id, "bob-id");
+}
+
+#[tokio::test]
+async fn private_messages_are_answered_privately() {
+    let (server, _client) = start(vec![Box::new(EchoBot)]).await;
+
+    let content = receipts::attach_id("!echo secret", "m1");
+    server.push_message("alice-id", "alice", &content).await;
+
+    let reply = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    assert_eq!(receipts::split_id(&reply.content).0, "secret");
+    assert_eq!(reply.client_id, "alice-id");
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(
+        !server
+            .posted()
+            .await
+            .iter()
+            .any(|m| m.plugin == "/broadcast")
+    );
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::MockServer;
+use rust_chat_client::chat::receipts;
+use rust_chat_client::types::Response;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{ChildStdin, ChildStdout, Command};
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+async fn write_line(stdin: &mut ChildStdin, line: &str) {
+    stdin
+        .write_all(format!("{line}\n").as_bytes())
+        .await
+        .unwrap();
+    stdin.flush().await.unwrap();
+}
+
+// reads JSON lines until one with the given name arrives
+async fn read_response(lines: &mut Lines<BufReader<ChildStdout>>, name: &str) -> Response {
+    tokio::time::timeout(TIMEOUT, async {
+        loop {
+            let line = lines.next_line().await.unwrap().expect("stdout was closed");
+            let rsp: Response = serde_json::from_str(&line).unwrap();
+            if rsp.rsp_name == name {
+                return rsp;
+            }
+        }
+    })
+    .await
+    .expect("the response was not written")
+}
+
+#[tokio::test]
+async fn json_lines_are_sent_and_responses_written() {
+    let server = MockServer::start().await;
+    let config = std::env::temp_dir().join("rust-chat-client-bridge-test/config.toml");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ChatClient"))
+        .args(["--server", &server.url, "--name", "tester", "--bridge"])
+        .env("CHAT_CLIENT_CONFIG", config)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+
+    write_line(&mut stdin, r#"{"type": "send", "text": "hello"}"#).await;
+    let posted = server.wait_for_post("/broadcast", TIMEOUT).await.unwrap();
+    assert_eq!(receipts::split_id(&posted.content).0, "hello");
+
+    // the sender of a message can be answered by name
+    server.push_message("bob-id", "bob", "hi tester").await;
+    let rsp = read_response(&mut lines, "bob").await;
+    assert_eq!(
+        (rsp.client_id.as_str(), rsp.content.as_str()),
+        ("bob-id", "hi tester")
+    );
+
+    write_line(
+        &mut stdin,
+        r#"{"type": "private", "user": "bob", "text": "psst"}"#,
+    )
+    .await;
+    let posted = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    assert_eq!(posted.client_id, "bob-id");
+    assert_eq!(receipts::split_id(&posted.content).0, "psst");
+
+    // broken lines are answered with an error and the bridge goes on
+    write_line(&mut stdin, "not json").await;
+    let rsp = read_response(&mut lines, "error Response").await;
+    assert!(rsp.err.starts_with("Invalid command"));
+
+    write_line(&mut stdin, r#"{"type": "quit"}"#).await;
+    let status = tokio::time::timeout(TIMEOUT, child.wait())
+        .await
+        .expect("the bridge did not exit")
+        .unwrap();
+    assert_eq!(status.code(), Some(0));
+}
@@ -0,0 +1,204 @@
+// MockServer imitates the routes of the Go-Chat-App server which the client uses.
+// Posted messages are recorded and responses can be queued for the response poller.
+use rust_chat_client::types::{IGNORE_RESPONSE_TAG, Message, Response};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, Notify};
+
+// how long a GET on the chat route waits before it answers with nothing
+const POLL_TIMEOUT: Duration = Duration::from_millis(200);
+
+#[derive(Default)]
+struct State {
+    posted: Mutex<Vec<Message>>,
+    inbox: Mutex<VecDeque<Response>>,
+    inbox_changed: Notify,
+    posted_changed: Notify,
+    // status of the answers to chat messages, 0 answers normally
+    run_status: AtomicU16,
+}
+
+pub struct MockServer {
+    pub url: String,
+    state: Arc<State>,
+}
+
+#[allow(dead_code)]
+impl MockServer {
+    pub async fn start() -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(State::default());
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_connection(stream, server_state.clone()));
+            }
+        });
+
+        MockServer { url, state }
+    }
+
+    // queues a response which the client receives through its poller
+    pub async fn push(&self, rsp: Response) {
+        self.state.inbox.lock().await.push_back(rsp);
+        self.state.inbox_changed.notify_waiters();
+    }
+
+    // a chat message of another user
+    pub async fn push_message(&self, client_id: &str, name: &str, content: &str) {
+        self.push(Response {
+            client_id: client_id.to_string(),
+            rsp_name: name.to_string(),
+            content: content.to_string(),
+            err: String::new(),
+        })
+        .await;
+    }
+
+    // answers chat messages with an error status until it is set back to 0
+    pub fn fail_runs(&self, status: u16) {
+        self.state.run_status.store(status, Ordering::SeqCst);
+    }
+
+    pub async fn posted(&self) -> Vec<Message> {
+        self.state.posted.lock().await.clone()
+    }
+
+    // waits until a message with the given plugin was posted
+    pub async fn wait_for_post(&self, plugin: &str, timeout: Duration) -> Option<Message> {
+        let posts = self.wait_for_posts(plugin, 1, timeout).await?;
+        posts.into_iter().next()
+    }
+
+    // waits until count messages with the given plugin were posted, returns all of them
+    pub async fn wait_for_posts(
+        &self,
+        plugin: &str,
+        count: usize,
+        timeout: Duration,
+    ) -> Option<Vec<Message>> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let notified = self.state.posted_changed.notified();
+                let posts: Vec<Message> = self
+                    .state
+                    .posted
+                    .lock()
+                    .await
+                    .iter()
+                    .filter(|m| m.plugin == plugin)
+                    .cloned()
+                    .collect();
+                if posts.len() >= count {
+                    return posts;
+                }
+                notified.await;
+            }
+        })
+        .await
+        .ok()
+    }
+}
+
+async fn handle_connection(stream: TcpStream, state: Arc<State>) {
+    let mut reader = BufReader::new(stream);
+    // the client keeps connections alive, so several requests are read
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+            return;
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let path = parts.next().unwrap_or_default().to_string();
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).await.unwrap_or(0) == 0 {
+                return;
+            }
+            if header == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+        let mut body = vec![0; content_length];
+        if reader.read_exact(&mut body).await.is_err() {
+            return;
+        }
+
+        let status = state.run_status.load(Ordering::SeqCst);
+        if status != 0 && method == "POST" && path.ends_with("/run") {
+            let http = format!("HTTP/1.1 {status} Error\r\nContent-Length: 0\r\n\r\n");
+            if reader.get_mut().write_all(http.as_bytes()).await.is_err() {
+                return;
+            }
+            continue;
+        }
+
+        let rsp = route(&state, &method, &path, &body).await;
+        let json = serde_json::to_string(&rsp).unwrap();
+        let http = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            json.len(),
+            json
+        );
+        if reader.get_mut().write_all(http.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn route(state: &State, method: &str, path: &str, body: &[u8]) -> Response {
+    let empty = Response {
+        client_id: String::new(),
+        rsp_name: String::new(),
+        content: String::new(),
+        err: String::new(),
+    };
+    match method {
+        "POST" => {
+            let Ok(msg) = serde_json::from_slice::<Message>(body) else {
+                return empty;
+            };
+            state.posted.lock().await.push(msg.clone());
+            state.posted_changed.notify_waiters();
+            if path.ends_with("/run") {
+                empty
+            } else {
+                // registration answers with the name and the auth token
+                Response {
+                    rsp_name: msg.name,
+                    content: String::from("token"),
+                    ..empty
+                }
+            }
+        }
+        "GET" => {
+            let notified = state.inbox_changed.notified();
+            if let Some(rsp) = state.inbox.lock().await.pop_front() {
+                return rsp;
+            }
+            let _ = tokio::time::timeout(POLL_TIMEOUT, notified).await;
+            match state.inbox.lock().await.pop_front() {
+                Some(rsp) => rsp,
+                None => Response {
+                    err: IGNORE_RESPONSE_TAG.to_string(),
+                    ..empty
+                },
+            }
+        }
+        _ => empty,
+    }
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::MockServer;
+use futures::StreamExt;
+use rust_chat_client::chat::{edits, receipts};
+use rust_chat_client::{Client, Event};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::test]
+async fn the_last_message_can_be_edited_and_deleted() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    assert!(client.execute("/edit too early").await.is_err());
+
+    client.send("helo").await.unwrap();
+    client.execute("/edit hello").await.unwrap();
+    client.execute("/delete").await.unwrap();
+
+    let posted = server
+        .wait_for_posts("/broadcast", 3, TIMEOUT)
+        .await
+        .expect("the messages were not sent");
+    let (text, id) = receipts::split_id(&posted[0].content);
+    let id = id.expect("the message has no id");
+    assert_eq!(text, "helo");
+    assert_eq!(posted[1].content, edits::edit(id, "hello"));
+    assert_eq!(posted[2].content, edits::delete(id));
+    // nothing is left to delete
+    assert!(client.execute("/delete").await.is_err());
+}
+
+#[tokio::test]
+async fn queued_messages_are_edited_in_the_outbox() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+
+    server.fail_runs(503);
+    client.send("helo").await.unwrap();
+    client.execute("/edit hello").await.unwrap();
+    server.fail_runs(0);
+
+    let posted = server
+        .wait_for_posts("/broadcast", 1, TIMEOUT)
+        .await
+        .expect("the messages were not sent");
+    assert_eq!(receipts::split_id(&posted[0].content).0, "hello");
+}
+
+#[tokio::test]
+async fn received_edits_become_events() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    server
+        .push_message("alice-id", "alice", &receipts::attach_group_id("hi", "m1"))
+        .await;
+    server
+        .push_message("alice-id", "alice", &edits::edit("m1", "hey"))
+        .await;
+    server
+        .push_message("alice-id", "alice", &edits::delete("m1"))
+        .await;
+
+    let received = tokio::time::timeout(TIMEOUT, async {
+        let mut received = Vec::new();
+        while received.len() < 3 {
+            match events.next().await {
+                Some(Event::Message {
+                    content,
+                    message_id,
+                    ..
+                }) => received.push(format!("message {message_id} {content}")),
+                Some(Event::Edited {
+                    message_id,
+                    content,
+                    ..
+                }) => received.push(format!("edited {message_id} {content}")),
+                Some(Event::Deleted { message_id, .. }) => {
+                    received.push(format!("deleted {message_id}"))
+                }
+                _ => {}
+            }
+        }
+        received
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        received,
+        vec!["message m1 hi", "edited m1 hey", "deleted m1"]
+    );
+}
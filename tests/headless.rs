@@ -0,0 +1,71 @@
+mod common;
+
+use common::MockServer;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+// runs the client binary in headless mode against the given server
+async fn run_headless(server_url: &str, args: &[&str]) -> std::process::ExitStatus {
+    let config = std::env::temp_dir().join("rust-chat-client-headless-test/config.toml");
+    let child = Command::new(env!("CARGO_BIN_EXE_ChatClient"))
+        .args(["--server", server_url, "--name", "tester"])
+        .args(args)
+        .env("CHAT_CLIENT_CONFIG", config)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    tokio::time::timeout(TIMEOUT, child.wait_with_output())
+        .await
+        .expect("the headless client did not exit")
+        .unwrap()
+        .status
+}
+
+#[tokio::test]
+async fn messages_to_an_unreachable_server_fail() {
+    let server = MockServer::start().await;
+    // the registration works, the message itself can't be sent. It must not be
+    // reported as sent, the queue would be lost when the client exits.
+    server.fail_runs(503);
+    let status = run_headless(&server.url, &["--send", "hello"]).await;
+    assert_eq!(status.code(), Some(1));
+}
+
+#[tokio::test]
+async fn sent_messages_exit_with_success() {
+    let server = MockServer::start().await;
+    let status = run_headless(&server.url, &["--send", "hello"]).await;
+    assert_eq!(status.code(), Some(0));
+    assert!(server.wait_for_post("/broadcast", TIMEOUT).await.is_some());
+}
+
+#[tokio::test]
+async fn invalid_arguments_exit_with_2() {
+    let server = MockServer::start().await;
+    // --replies needs --wait
+    let status = run_headless(&server.url, &["--send", "hello", "--replies", "1"]).await;
+    assert_eq!(status.code(), Some(2));
+}
+
+#[tokio::test]
+async fn unreachable_servers_exit_with_3() {
+    // nothing listens on the discard port
+    let status = run_headless("http://127.0.0.1:9", &["--send", "hello"]).await;
+    assert_eq!(status.code(), Some(3));
+}
+
+#[tokio::test]
+async fn missing_replies_exit_with_4() {
+    let server = MockServer::start().await;
+    let status = run_headless(
+        &server.url,
+        &["--send", "anyone?", "--wait", "1", "--replies", "1"],
+    )
+    .await;
+    assert_eq!(status.code(), Some(4));
+}
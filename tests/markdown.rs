@@ -0,0 +1,137 @@
+use rust_chat_client::chat::markdown::{self, Block, Format, Inline};
+
+fn inline(text: &str, format: Format) -> Inline {
+    Inline {
+        text: text.to_string(),
+        format,
+    }
+}
+
+const BOLD: Format = Format {
+    bold: true,
+    italic: false,
+    code: false,
+    strike: false,
+    link: false,
+};
+
+#[test]
+fn inline_formats_are_parsed() {
+    let plain = Format::default();
+    assert_eq!(
+        markdown::parse_inline("a **b** c"),
+        [inline("a ", plain), inline("b", BOLD), inline(" c", plain)]
+    );
+    assert_eq!(
+        markdown::parse_inline("*it* ~~no~~ `x * y`"),
+        [
+            inline(
+                "it",
+                Format {
+                    italic: true,
+                    ..plain
+                }
+            ),
+            inline(" ", plain),
+            inline(
+                "no",
+                Format {
+                    strike: true,
+                    ..plain
+                }
+            ),
+            inline(" ", plain),
+            inline(
+                "x * y",
+                Format {
+                    code: true,
+                    ..plain
+                }
+            ),
+        ]
+    );
+    // nested formats
+    assert_eq!(
+        markdown::parse_inline("**_both_**"),
+        [inline(
+            "both",
+            Format {
+                italic: true,
+                ..BOLD
+            }
+        )]
+    );
+}
+
+#[test]
+fn unclosed_markers_stay_text() {
+    let plain = Format::default();
+    for text in [
+        "2 * 3 = 6",
+        "a ** b",
+        "snake_case_name",
+        "`open",
+        "\\*escaped\\*",
+    ] {
+        let inlines = markdown::parse_inline(text);
+        assert!(inlines.iter().all(|i| i.format == plain), "{text}");
+    }
+    assert_eq!(
+        markdown::parse_inline("\\*escaped\\*"),
+        [inline("*escaped*", plain)]
+    );
+}
+
+#[test]
+fn links_are_found_in_the_text() {
+    let link = Format {
+        link: true,
+        ..Format::default()
+    };
+    assert_eq!(
+        markdown::parse_inline("see https://example.org/a_b_c."),
+        [
+            inline("see ", Format::default()),
+            inline("https://example.org/a_b_c", link),
+            inline(".", Format::default()),
+        ]
+    );
+    // links inside code are not links
+    assert_eq!(
+        markdown::parse_inline("`https://example.org`"),
+        [inline(
+            "https://example.org",
+            Format {
+                code: true,
+                ..Format::default()
+            }
+        )]
+    );
+    assert!(
+        markdown::parse_inline("https:// alone")
+            .iter()
+            .all(|i| !i.format.link)
+    );
+}
+
+#[test]
+fn fenced_code_blocks_keep_their_lines() {
+    let blocks = markdown::parse("look:\n```rust\n    let x = **1**;\n```\ndone");
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(
+        blocks[1],
+        Block::Code {
+            language: String::from("rust"),
+            lines: vec![String::from("    let x = **1**;")],
+        }
+    );
+    // a block which is not closed ends with the message
+    let blocks = markdown::parse("```\nfn main() {}");
+    assert_eq!(
+        blocks,
+        [Block::Code {
+            language: String::new(),
+            lines: vec![String::from("fn main() {}")],
+        }]
+    );
+}
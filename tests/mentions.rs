@@ -0,0 +1,42 @@
+use rust_chat_client::chat::mentions;
+
+#[test]
+fn mentions_are_parsed() {
+    let found: Vec<&str> = mentions::parse("@alice and @bob-2. ask @carol_x!")
+        .iter()
+        .map(|m| m.name)
+        .collect();
+    assert_eq!(found, ["alice", "bob-2", "carol_x"]);
+
+    let mention = &mentions::parse("hi @bob")[0];
+    assert_eq!(mention.range, 3..7);
+}
+
+#[test]
+fn mail_addresses_are_no_mentions() {
+    assert!(mentions::parse("write to bob@example.org").is_empty());
+    assert!(mentions::parse("a lone @ sign").is_empty());
+}
+
+#[test]
+fn own_mentions_ignore_the_case() {
+    assert!(mentions::mentions("thanks @Alice", "alice"));
+    assert!(!mentions::mentions("thanks @alicia", "alice"));
+    assert!(!mentions::mentions("thanks @alice", ""));
+    assert_eq!(
+        mentions::mentions_of("@bob @alice @ALICE", "alice").len(),
+        2
+    );
+}
+
+#[test]
+fn names_are_completed() {
+    let names = ["bob", "Alice", "alfred", "carol", "alice"];
+    assert_eq!(
+        mentions::complete("al", &names),
+        ["alfred", "Alice", "alice"]
+    );
+    assert_eq!(mentions::complete("AL", &names).len(), 3);
+    assert!(mentions::complete("dave", &names).is_empty());
+    assert_eq!(mentions::complete("", &names).len(), 5);
+}
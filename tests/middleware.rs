@@ -0,0 +1,124 @@
+mod common;
+
+use async_trait::async_trait;
+use common::MockServer;
+use futures::StreamExt;
+use rust_chat_client::chat::receipts;
+use rust_chat_client::types::{ChatErrorWithMsg, Message, Response};
+use rust_chat_client::{Client, Event, Middleware};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// appends its tag to outgoing chat messages and to incoming messages
+struct Tag(&'static str);
+
+#[async_trait]
+impl Middleware for Tag {
+    async fn outgoing(&self, mut msg: Message) -> Result<Option<Message>, ChatErrorWithMsg> {
+        msg.content.push_str(self.0);
+        Ok(Some(msg))
+    }
+
+    async fn incoming(&self, mut rsp: Response) -> Option<Response> {
+        rsp.content.push_str(self.0);
+        Some(rsp)
+    }
+}
+
+// drops everything which contains "secret"
+struct Filter;
+
+#[async_trait]
+impl Middleware for Filter {
+    async fn outgoing(&self, msg: Message) -> Result<Option<Message>, ChatErrorWithMsg> {
+        Ok((!msg.content.contains("secret")).then_some(msg))
+    }
+
+    async fn incoming(&self, rsp: Response) -> Option<Response> {
+        (!rsp.content.contains("secret")).then_some(rsp)
+    }
+}
+
+#[tokio::test]
+async fn outgoing_messages_pass_the_chain_in_order() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone())
+        .middleware("a", Tag(" a"))
+        .middleware("b", Tag(" b"))
+        .build()
+        .await;
+    client.register("tester").await.unwrap();
+
+    client.send("hello").await.unwrap();
+
+    let posted = server.wait_for_post("/broadcast", TIMEOUT).await.unwrap();
+    // the id is appended after the middleware
+    assert_eq!(receipts::split_id(&posted.content).0, "hello a b");
+    // the registration is not changed
+    let register = server.wait_for_post("/register", TIMEOUT).await.unwrap();
+    assert_eq!(register.content, "tester");
+}
+
+#[tokio::test]
+async fn dropped_and_disabled_middleware() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone())
+        .middleware("filter", Filter)
+        .middleware("tag", Tag(" tagged"))
+        .build()
+        .await;
+    client.register("tester").await.unwrap();
+    let chain = &client.user_service().chat_client.http_client.middleware;
+    assert!(chain.set_enabled("tag", false));
+    assert!(!chain.set_enabled("unknown", false));
+
+    client.send("the secret plan").await.unwrap();
+    client.send("lunch?").await.unwrap();
+
+    let posted = server.wait_for_post("/broadcast", TIMEOUT).await.unwrap();
+    assert_eq!(receipts::split_id(&posted.content).0, "lunch?");
+    assert_eq!(server.posted().await.len(), 2);
+}
+
+#[tokio::test]
+async fn incoming_responses_pass_the_chain_in_reverse() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone())
+        .middleware("filter", Filter)
+        .middleware("a", Tag(" a"))
+        .middleware("b", Tag(" b"))
+        .build()
+        .await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    server.push_message("alice-id", "alice", "a secret").await;
+    server.push_message("alice-id", "alice", "hi").await;
+
+    let event = tokio::time::timeout(TIMEOUT, async {
+        loop {
+            if let Some(Event::Message { content, .. }) = events.next().await {
+                return content;
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(event, "hi b a");
+}
+
+#[tokio::test]
+async fn middleware_can_be_enabled_by_the_builder() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone())
+        .middleware_enabled("emoji", true)
+        .build()
+        .await;
+    client.register("tester").await.unwrap();
+
+    client.send("done :tada:").await.unwrap();
+
+    let posted = server.wait_for_post("/broadcast", TIMEOUT).await.unwrap();
+    assert_eq!(receipts::split_id(&posted.content).0, "done 🎉");
+}
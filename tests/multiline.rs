@@ -0,0 +1,41 @@
+mod common;
+
+use common::MockServer;
+use rust_chat_client::Client;
+use rust_chat_client::chat::receipts;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::test]
+async fn messages_keep_their_line_breaks() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+
+    // the input of the UI, a trailing line break is dropped
+    client
+        .execute("first line\n  indented\n\nlast line\n")
+        .await
+        .unwrap();
+
+    let posted = server.wait_for_post("/broadcast", TIMEOUT).await.unwrap();
+    let (text, _) = receipts::split_id(&posted.content);
+    assert_eq!(text, "first line\n  indented\n\nlast line");
+}
+
+#[tokio::test]
+async fn private_messages_keep_their_line_breaks() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+
+    client
+        .execute("/private bob-id ```\nfn main() {}\n```")
+        .await
+        .unwrap();
+
+    let posted = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    let (text, _) = receipts::split_id(&posted.content);
+    assert_eq!(text, "```\nfn main() {}\n```");
+}
@@ -0,0 +1,22 @@
+use rust_chat_client::helper;
+
+#[test]
+fn initials_of_names() {
+    assert_eq!(helper::initials("alice"), "AL");
+    assert_eq!(helper::initials("john_doe"), "JD");
+    assert_eq!(helper::initials("Mary Ann Smith"), "MA");
+    assert_eq!(helper::initials("x"), "X");
+    assert_eq!(helper::initials("-- jörg"), "JÖ");
+    assert_eq!(helper::initials(""), "?");
+}
+
+#[test]
+fn hash_is_stable() {
+    // the colors must not change between runs or clients
+    assert_eq!(helper::stable_hash(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(helper::stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    assert_ne!(
+        helper::stable_hash("client-1"),
+        helper::stable_hash("client-2")
+    );
+}
@@ -0,0 +1,81 @@
+use rust_chat_client::chat::notifications::{
+    self, DesktopNotification, Notification, NotificationKind,
+};
+use rust_chat_client::config::settings::Settings;
+
+fn mention() -> Notification {
+    Notification {
+        kind: NotificationKind::Mention,
+        name: String::from("alice"),
+        text: String::from("hi @bob"),
+        place: String::from("team"),
+    }
+}
+
+#[test]
+fn escape_sequences_follow_the_settings() {
+    let notification = mention();
+    assert_eq!(
+        notifications::escape_sequences(true, DesktopNotification::Off, &notification),
+        "\x07"
+    );
+    assert_eq!(
+        notifications::escape_sequences(false, DesktopNotification::Osc9, &notification),
+        "\x1b]9;alice: hi @bob\x07"
+    );
+    assert_eq!(
+        notifications::escape_sequences(false, DesktopNotification::Osc777, &notification),
+        "\x1b]777;notify;alice;hi @bob\x07"
+    );
+}
+
+#[test]
+fn control_chars_cannot_end_the_sequence() {
+    let notification = Notification {
+        text: String::from("evil\x07\x1b]0;title"),
+        ..mention()
+    };
+    let sequences =
+        notifications::escape_sequences(false, DesktopNotification::Osc9, &notification);
+    assert_eq!(sequences.matches('\x07').count(), 1);
+    assert!(!sequences[1..].contains('\x1b'));
+}
+
+#[test]
+fn notifications_are_configured_in_the_config() {
+    let settings: Settings = toml::from_str(
+        r#"
+        [notifications]
+        events = ["private"]
+        desktop = "osc777"
+        command = "true"
+        "#,
+    )
+    .unwrap();
+    let notifications = settings.notifications;
+    assert_eq!(notifications.events, [NotificationKind::Private]);
+    assert_eq!(notifications.desktop, DesktopNotification::Osc777);
+    assert_eq!(notifications.command.as_deref(), Some("true"));
+    // missing keys keep their defaults
+    assert!(notifications.bell);
+    assert!(!notifications.when_focused);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn hooks_get_the_event_as_environment() {
+    let path = std::env::temp_dir().join(format!("chat-hook-{}", std::process::id()));
+    let command = format!(
+        "printf '%s|%s|%s|%s' \"$CHAT_EVENT\" \"$CHAT_NAME\" \"$CHAT_TEXT\" \"$CHAT_PLACE\" > {}",
+        path.display()
+    );
+    let status = notifications::run_hook(&command, &mention())
+        .unwrap()
+        .wait()
+        .await
+        .unwrap();
+    assert!(status.success());
+    let output = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(output, "mention|alice|hi @bob|team");
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use common::MockServer;
+use futures::StreamExt;
+use rust_chat_client::chat::outbox::{Outbox, OutboxState, OutboxUpdate};
+use rust_chat_client::chat::receipts;
+use rust_chat_client::{ChatError, Client, Event, EventStream};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn next_outbox(events: &mut EventStream) -> OutboxUpdate {
+    tokio::time::timeout(TIMEOUT, async {
+        loop {
+            if let Some(Event::Outbox(update)) = events.next().await {
+                return update;
+            }
+        }
+    })
+    .await
+    .expect("no outbox event arrived")
+}
+
+// the texts of the first count broadcasts
+async fn posted_broadcasts(server: &MockServer, count: usize) -> Vec<String> {
+    server
+        .wait_for_posts("/broadcast", count, TIMEOUT)
+        .await
+        .expect("the messages were not sent")
+        .iter()
+        .map(|m| receipts::split_id(&m.content).0.to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn queued_messages_are_sent_in_order() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    server.fail_runs(503);
+    client.send("one").await.unwrap();
+    client.send("two").await.unwrap();
+    let first = next_outbox(&mut events).await;
+    assert_eq!(
+        (first.state, first.message.text.as_str()),
+        (OutboxState::Queued, "one")
+    );
+    assert_eq!(next_outbox(&mut events).await.message.text, "two");
+    assert_eq!(client.user_service().chat_client.outbox.messages().len(), 2);
+
+    server.fail_runs(0);
+    // waits behind the queued messages
+    client.send("three").await.unwrap();
+
+    assert_eq!(
+        posted_broadcasts(&server, 3).await,
+        vec!["one", "two", "three"]
+    );
+    // the message is removed from the outbox after the server answered
+    loop {
+        let update = next_outbox(&mut events).await;
+        if update.state == OutboxState::Sent && update.message.text == "three" {
+            break;
+        }
+    }
+    assert!(
+        client
+            .user_service()
+            .chat_client
+            .outbox
+            .messages()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn texts_in_brackets_are_queued() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    server.fail_runs(503);
+    client.send("[WIP] done?").await.unwrap();
+    let queued = next_outbox(&mut events).await;
+    assert_eq!(
+        (queued.state, queued.message.text.as_str()),
+        (OutboxState::Queued, "[WIP] done?")
+    );
+
+    server.fail_runs(0);
+    assert_eq!(posted_broadcasts(&server, 1).await, vec!["[WIP] done?"]);
+}
+
+#[tokio::test]
+async fn failed_messages_can_be_resent_or_cancelled() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    server.fail_runs(503);
+    client.send("rejected").await.unwrap();
+    client.send("dropped").await.unwrap();
+    server.fail_runs(400);
+
+    let mut failed = Vec::new();
+    while failed.len() < 2 {
+        let update = next_outbox(&mut events).await;
+        if update.state == OutboxState::Failed {
+            failed.push(update.message);
+        }
+    }
+    assert!(failed.iter().all(|m| m.error.is_some()));
+
+    server.fail_runs(0);
+    client
+        .execute(&format!("/outbox cancel {}", failed[1].id))
+        .await
+        .unwrap();
+    client
+        .execute(&format!("/outbox resend {}", failed[0].id))
+        .await
+        .unwrap();
+
+    assert_eq!(posted_broadcasts(&server, 1).await, vec!["rejected"]);
+    assert!(
+        client
+            .execute(&format!("/outbox cancel {}", failed[1].id))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn a_disabled_outbox_fails_offline_messages() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    client.user_service().chat_client.outbox.disable();
+
+    server.fail_runs(503);
+    let err = client.send("lost on exit").await.unwrap_err();
+    assert!(matches!(err.kind, ChatError::Offline));
+    assert!(
+        client
+            .user_service()
+            .chat_client
+            .outbox
+            .messages()
+            .is_empty()
+    );
+}
+
+#[test]
+fn private_messages_are_not_loaded_again() {
+    let path = std::env::temp_dir().join(format!("chat-outbox-{}.json", std::process::id()));
+    // the file of an earlier run
+    let saved = r#"{"next_id": 2, "messages": [
+        {"id": 1, "plugin": "/broadcast", "content": "hello", "text": "hello"},
+        {"id": 2, "plugin": "/private", "content": "secret", "text": "secret", "receiver": "old-client-id"}
+    ]}"#;
+    std::fs::write(&path, saved).unwrap();
+
+    // the receiver has another client id after a restart
+    let outbox = Outbox::new();
+    outbox.persist_to(path.clone()).unwrap();
+    let texts: Vec<String> = outbox.messages().into_iter().map(|m| m.text).collect();
+    assert_eq!(texts, vec!["hello"]);
+    let _ = std::fs::remove_file(path);
+}
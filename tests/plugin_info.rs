@@ -0,0 +1,60 @@
+use rust_chat_client::{ArgKind, ChatError, PluginInfo};
+
+fn private() -> PluginInfo {
+    PluginInfo::new("/private", "sends a private message")
+        .alias("/msg")
+        .arg("client_id", ArgKind::Word)
+        .arg("text", ArgKind::Text)
+}
+
+fn outbox() -> PluginInfo {
+    PluginInfo::new("/outbox", "lists the queue")
+        .optional_arg(
+            "action",
+            ArgKind::Choice(vec![String::from("resend"), String::from("cancel")]),
+        )
+        .optional_arg("id", ArgKind::Number)
+}
+
+// the error is a usage error which shows how the command is used
+fn assert_usage_error(info: &PluginInfo, content: &str) {
+    let err = info.check(content).unwrap_err();
+    assert!(matches!(err.kind, ChatError::WrongInput), "{content}");
+    assert!(err.msg.contains(&info.usage()), "{}", err.msg);
+}
+
+#[test]
+fn usage_shows_required_and_optional_args() {
+    assert_eq!(private().usage(), "/private {client_id} {text}");
+    assert_eq!(outbox().usage(), "/outbox [resend|cancel] [id]");
+    assert_eq!(PluginInfo::new("/quit", "").usage(), "/quit");
+}
+
+#[test]
+fn missing_args_are_reported() {
+    assert_usage_error(&private(), "");
+    assert_usage_error(&private(), "  abc  ");
+    assert!(outbox().check("").is_ok());
+    assert!(outbox().check("resend").is_ok());
+}
+
+#[test]
+fn extra_args_are_reported() {
+    assert_usage_error(&outbox(), "resend 3 4");
+    assert_usage_error(&PluginInfo::new("/quit", ""), "now");
+}
+
+#[test]
+fn text_takes_the_rest_of_the_input() {
+    assert!(private().check("abc hello there,  how are you?").is_ok());
+    let info = PluginInfo::new("/note", "").optional_arg("text", ArgKind::Text);
+    assert!(info.check("").is_ok());
+    assert!(info.check("a b c").is_ok());
+}
+
+#[test]
+fn numbers_and_choices_are_checked() {
+    assert!(outbox().check("cancel 12").is_ok());
+    assert_usage_error(&outbox(), "cancel twelve");
+    assert_usage_error(&outbox(), "delete 12");
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::MockServer;
+use futures::StreamExt;
+use rust_chat_client::chat::control;
+use rust_chat_client::chat::reactions::{self, Reaction, ReactionCount, Reactions};
+use rust_chat_client::{Client, Event};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::test]
+async fn reacting_twice_takes_the_reaction_back() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+
+    client.execute("/react m1 👍").await.unwrap();
+    client.execute("/react m1 👍").await.unwrap();
+    client.execute("/react m2 🎉 bob-id").await.unwrap();
+
+    let group = server
+        .wait_for_posts("/broadcast", 2, TIMEOUT)
+        .await
+        .expect("the reactions were not sent");
+    assert_eq!(group[0].content, reactions::reaction("m1", "👍", true));
+    assert_eq!(group[1].content, reactions::reaction("m1", "👍", false));
+    // reactions to private messages only go to the other client
+    let private = server
+        .wait_for_posts("/private", 1, TIMEOUT)
+        .await
+        .expect("the reactions were not sent");
+    assert_eq!(private[0].content, reactions::reaction("m2", "🎉", true));
+    assert_eq!(private[0].client_id, "bob-id");
+
+    assert!(client.execute("/react m1").await.is_err());
+}
+
+#[tokio::test]
+async fn received_reactions_become_events() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    server
+        .push_message("alice-id", "alice", &reactions::reaction("m1", "🔥", true))
+        .await;
+    server
+        .push_message("alice-id", "alice", &reactions::reaction("m1", "🔥", false))
+        .await;
+
+    let received = tokio::time::timeout(TIMEOUT, async {
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            if let Some(Event::Reaction {
+                client_id,
+                message_id,
+                emoji,
+                added,
+            }) = events.next().await
+            {
+                received.push(format!("{client_id} {message_id} {emoji} {added}"));
+            }
+        }
+        received
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        received,
+        vec!["alice-id m1 🔥 true", "alice-id m1 🔥 false"]
+    );
+}
+
+#[test]
+fn reactions_are_counted_per_client() {
+    let mut counted = Reactions::new();
+    let thumbs = Reaction {
+        id: "m1",
+        emoji: "👍",
+        added: true,
+    };
+    assert!(counted.apply("alice", thumbs));
+    assert!(counted.apply("bob", thumbs));
+    // the same client is only counted once
+    assert!(!counted.apply("bob", thumbs));
+    assert!(counted.apply(
+        "alice",
+        Reaction {
+            emoji: "🎉",
+            ..thumbs
+        }
+    ));
+    assert!(counted.apply(
+        "bob",
+        Reaction {
+            added: false,
+            ..thumbs
+        }
+    ));
+
+    assert_eq!(
+        counted.counts("m1", "alice"),
+        vec![
+            ReactionCount {
+                emoji: String::from("👍"),
+                count: 1,
+                own: true,
+            },
+            ReactionCount {
+                emoji: String::from("🎉"),
+                count: 1,
+                own: true,
+            },
+        ]
+    );
+    assert!(counted.counts("m2", "alice").is_empty());
+    assert_eq!(
+        reactions::parse(&reactions::reaction("m1", "👍", true)),
+        Some(thumbs)
+    );
+    assert_eq!(reactions::parse(&control::control("[react:m1]")), None);
+    // typed text is no reaction
+    assert_eq!(reactions::parse("[react:m1] 👍"), None);
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::MockServer;
+use futures::StreamExt;
+use rust_chat_client::chat::control;
+use rust_chat_client::chat::receipts::{self, DeliveryStatus};
+use rust_chat_client::{Client, Event, EventStream};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn next_delivery(events: &mut EventStream) -> (String, String, DeliveryStatus) {
+    tokio::time::timeout(TIMEOUT, async {
+        loop {
+            if let Some(Event::Delivery {
+                client_id,
+                message_id,
+                status,
+            }) = events.next().await
+            {
+                return (client_id, message_id, status);
+            }
+        }
+    })
+    .await
+    .expect("no delivery event arrived")
+}
+
+#[tokio::test]
+async fn private_messages_get_an_id_and_receipts() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    client.send_private("bob-id", "hi bob").await.unwrap();
+
+    let posted = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    assert!(!posted.message_id.is_empty());
+    assert_eq!(
+        receipts::split_id(&posted.content),
+        ("hi bob", Some(posted.message_id.as_str()))
+    );
+    let (client_id, id, status) = next_delivery(&mut events).await;
+    assert_eq!(
+        (client_id.as_str(), id.as_str(), status),
+        (
+            "bob-id",
+            posted.message_id.as_str(),
+            DeliveryStatus::Sending
+        )
+    );
+
+    let read = receipts::receipt(DeliveryStatus::Read, &posted.message_id).unwrap();
+    server.push_message("bob-id", "bob", &read).await;
+    let (client_id, id, status) = next_delivery(&mut events).await;
+    assert_eq!(
+        (client_id.as_str(), id.as_str(), status),
+        ("bob-id", posted.message_id.as_str(), DeliveryStatus::Read)
+    );
+}
+
+#[tokio::test]
+async fn texts_in_brackets_are_chat_messages() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+
+    client.send_private("bob-id", "[WIP] done?").await.unwrap();
+
+    let posted = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    assert!(!posted.message_id.is_empty());
+    assert_eq!(receipts::split_id(&posted.content).0, "[WIP] done?");
+}
+
+#[tokio::test]
+async fn typed_control_messages_are_escaped() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    let delivered = receipts::receipt(DeliveryStatus::Delivered, "abc").unwrap();
+    client.send_private("bob-id", &delivered).await.unwrap();
+
+    let posted = server.wait_for_post("/private", TIMEOUT).await.unwrap();
+    let (text, _) = receipts::split_id(&posted.content);
+    assert!(!control::is_control(text));
+    assert_eq!(receipts::parse_receipt(text), None);
+
+    // the receiver sees the text as it was typed
+    server.push_message("bob-id", "bob", &posted.content).await;
+    let content = tokio::time::timeout(TIMEOUT, async {
+        loop {
+            if let Some(Event::Message { content, .. }) = events.next().await {
+                return content;
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(content, delivered);
+}
+
+#[tokio::test]
+async fn received_messages_hide_the_id() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    server
+        .push_message("bob-id", "bob", &receipts::attach_id("psst", "xyz"))
+        .await;
+
+    let content = tokio::time::timeout(TIMEOUT, async {
+        loop {
+            if let Some(Event::Message { content, .. }) = events.next().await {
+                return content;
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(content, "psst");
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::MockServer;
+use futures::StreamExt;
+use rust_chat_client::chat::receipts;
+use rust_chat_client::chat::replies::{self, ReplyTo};
+use rust_chat_client::{Client, Event};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::test]
+async fn replies_carry_their_parent() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+
+    let parent = ReplyTo::new("m1", "alice", "how about lunch?");
+    client.reply(parent.clone(), "sounds good").await.unwrap();
+
+    let posted = server
+        .wait_for_post("/broadcast", TIMEOUT)
+        .await
+        .expect("the reply was not sent");
+    let (text, id) = receipts::split_id(&posted.content);
+    assert!(id.is_some());
+    assert_eq!(replies::split_reply(text), (Some(parent), "sounds good"));
+}
+
+#[tokio::test]
+async fn queued_replies_keep_their_parent() {
+    let server = MockServer::start().await;
+    let client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+
+    server.fail_runs(503);
+    let parent = ReplyTo::new("m1", "alice", "how about lunch?");
+    client.reply(parent.clone(), "sounds good").await.unwrap();
+    server.fail_runs(0);
+
+    let posted = server
+        .wait_for_post("/broadcast", TIMEOUT)
+        .await
+        .expect("the reply was not sent");
+    let (text, _) = receipts::split_id(&posted.content);
+    assert_eq!(replies::split_reply(text), (Some(parent), "sounds good"));
+}
+
+#[tokio::test]
+async fn received_replies_become_events() {
+    let server = MockServer::start().await;
+    let mut client = Client::builder(server.url.clone()).build().await;
+    client.register("tester").await.unwrap();
+    let mut events = client.events().unwrap();
+
+    let parent = ReplyTo::new("m1", "alice", "how about lunch?");
+    let content = receipts::attach_group_id(&replies::attach_reply("sure", &parent), "m2");
+    server.push_message("bob-id", "bob", &content).await;
+
+    let event = tokio::time::timeout(TIMEOUT, async {
+        loop {
+            if let Some(Event::Message {
+                content,
+                message_id,
+                reply_to,
+                ..
+            }) = events.next().await
+            {
+                return (content, message_id, reply_to);
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        event,
+        (String::from("sure"), String::from("m2"), Some(parent))
+    );
+}
+
+#[test]
+fn long_parents_are_cut_off() {
+    let parent = ReplyTo::new("m1", "alice", &"word ".repeat(20));
+    assert_eq!(parent.excerpt.chars().count(), 40);
+    assert!(parent.excerpt.ends_with('…'));
+
+    let parent = ReplyTo::new("m1", "alice", "two\nlines");
+    assert_eq!(parent.excerpt, "two lines");
+    // texts without a parent are unchanged
+    assert_eq!(replies::split_reply("hello"), (None, "hello"));
+}
@@ -0,0 +1,104 @@
+use rust_chat_client::{ChatError, Client};
+use std::path::PathBuf;
+
+// an empty scripts directory for one test
+fn scripts_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("chat-scripts-{test}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+async fn client(dir: &PathBuf) -> Client {
+    Client::builder("http://127.0.0.1:9")
+        .scripts_dir(dir)
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn scripts_are_only_loaded_from_the_given_dir() {
+    let client = Client::builder("http://127.0.0.1:9").build().await;
+    assert!(
+        client
+            .user_service()
+            .plugin_registry
+            .scripts
+            .commands()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn reload_picks_up_new_scripts() {
+    let dir = scripts_dir("reload");
+    let client = client(&dir).await;
+    assert!(client.execute("/shout hi").await.is_err());
+
+    std::fs::write(
+        dir.join("shout.rhai"),
+        r#"register("/shout", "Shouts"); fn run(args) { args.to_upper() }"#,
+    )
+    .unwrap();
+    client.execute("/plugins reload").await.unwrap();
+    assert_eq!(client.execute("/shout hi").await.unwrap(), "HI");
+
+    std::fs::remove_file(dir.join("shout.rhai")).unwrap();
+    client.execute("/plugins reload").await.unwrap();
+    assert!(!client.user_service().plugin_registry.scripts.has("/shout"));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn endless_scripts_are_stopped() {
+    let dir = scripts_dir("limits");
+    std::fs::write(
+        dir.join("limits.rhai"),
+        r#"
+        register("/loop", "Runs forever", "endless");
+        register("/deep", "Calls itself", "deep");
+        register("/grow", "Builds a huge string", "grow");
+        fn endless(args) { loop {} }
+        fn recurse(n) { recurse(n + 1) }
+        fn deep(args) { recurse(0) }
+        fn grow(args) { let s = "x"; loop { s += s; } }
+        "#,
+    )
+    .unwrap();
+    let client = client(&dir).await;
+
+    for command in ["/loop", "/deep", "/grow"] {
+        let err = client.execute(command).await.unwrap_err();
+        assert!(matches!(err.kind, ChatError::PluginError), "{command}");
+    }
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn scripts_cant_eval_or_import() {
+    let dir = scripts_dir("sandbox");
+    let other = dir.join("other.rhai");
+    std::fs::write(&other, r#"fn answer() { 42 }"#).unwrap();
+    std::fs::write(
+        dir.join("eval.rhai"),
+        r#"register("/eval", "Evaluates"); fn run(args) { eval(args) }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("import.rhai"),
+        format!(
+            r#"import "{}" as other; register("/import", "Imports"); fn run(args) {{ 1 }}"#,
+            other.with_extension("").display()
+        ),
+    )
+    .unwrap();
+    let client = client(&dir).await;
+
+    let scripts = client.user_service().plugin_registry.scripts.clone();
+    let (count, errors) = scripts.load();
+    assert_eq!(count, 0);
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(!scripts.has("/eval"));
+    assert!(!scripts.has("/import"));
+    let _ = std::fs::remove_dir_all(dir);
+}